@@ -0,0 +1,22 @@
+//! Caesar 基准测试：对比查表实现（`Caesar`）与逐字符实现（`caesar::encrypt`）
+//!
+//! 运行方式：`cargo bench --features bench`
+
+use ciphery::Cipher;
+use ciphery::caesar::{self, Caesar};
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+
+fn bench_caesar(c: &mut Criterion) {
+    let ascii = "The quick brown fox jumps over the lazy dog. ".repeat(20_000);
+    let cipher = Caesar::new(13);
+
+    c.bench_function("caesar_table_ascii", |b| {
+        b.iter(|| cipher.encrypt(black_box(&ascii)).unwrap())
+    });
+    c.bench_function("caesar_chars_ascii", |b| {
+        b.iter(|| caesar::encrypt(black_box(&ascii), 13))
+    });
+}
+
+criterion_group!(benches, bench_caesar);
+criterion_main!(benches);
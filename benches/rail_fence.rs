@@ -0,0 +1,21 @@
+//! Rail Fence 基准测试：大段输入的加密与解密
+//!
+//! 运行方式：`cargo bench --features bench`
+
+use ciphery::rail_fence;
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+
+fn bench_rail_fence(c: &mut Criterion) {
+    let plain = "WE ARE DISCOVERED, FLEE AT ONCE! ".repeat(30_000);
+    let encrypted = rail_fence::encrypt(&plain, 5);
+
+    c.bench_function("rail_fence_encrypt", |b| {
+        b.iter(|| rail_fence::encrypt(black_box(&plain), 5))
+    });
+    c.bench_function("rail_fence_decrypt", |b| {
+        b.iter(|| rail_fence::decrypt(black_box(&encrypted), 5))
+    });
+}
+
+criterion_group!(benches, bench_rail_fence);
+criterion_main!(benches);
@@ -0,0 +1,246 @@
+//! 密码分析 (Cryptanalysis) 辅助工具
+//!
+//! 提供基于英文字母频率的打分函数，供暴力破解等功能挑选"最像英文"的候选结果。
+
+use crate::{atbash, caesar};
+use std::fmt;
+
+/// 英文文本中 A-Z 各字母的出现频率（百分比）
+const ENGLISH_FREQUENCIES: [f64; 26] = [
+    8.167, 1.492, 2.782, 4.253, 12.702, 2.228, 2.015, 6.094, 6.966, 0.153, 0.772, 4.025, 2.406,
+    6.749, 7.507, 1.929, 0.095, 5.987, 6.327, 9.056, 2.758, 0.978, 2.360, 0.150, 1.974, 0.074,
+];
+
+/// 统计文本中 A-Z 各字母出现的次数，忽略大小写与非字母字符
+///
+/// 返回数组的下标 0 对应 `a`，25 对应 `z`。
+///
+/// # 示例
+///
+/// ```
+/// use ciphery::analysis::letter_frequency;
+/// let counts = letter_frequency("Aa-b!");
+/// assert_eq!((counts[0], counts[1], counts[2]), (2, 1, 0));
+/// ```
+pub fn letter_frequency(text: &str) -> [u32; 26] {
+    let mut counts = [0u32; 26];
+    for c in text.chars() {
+        if c.is_ascii_alphabetic() {
+            counts[(c.to_ascii_lowercase() as u8 - b'a') as usize] += 1;
+        }
+    }
+    counts
+}
+
+/// 计算文本与标准英文字母频率之间的卡方 (chi-squared) 统计量
+///
+/// 忽略大小写与非字母字符。返回值越小，说明文本越"像英文"；
+/// 如果文本中没有任何字母，返回 `f64::INFINITY`。
+///
+/// # 示例
+///
+/// ```
+/// use ciphery::analysis::english_score;
+/// assert!(english_score("the quick brown fox") < english_score("xqz jvk wpq"));
+/// ```
+pub fn english_score(text: &str) -> f64 {
+    let counts = letter_frequency(text);
+    let total: u32 = counts.iter().sum();
+    if total == 0 {
+        return f64::INFINITY;
+    }
+
+    counts
+        .iter()
+        .zip(ENGLISH_FREQUENCIES.iter())
+        .map(|(&observed, &freq)| {
+            let expected = total as f64 * freq / 100.0;
+            let diff = observed as f64 - expected;
+            diff * diff / expected
+        })
+        .sum()
+}
+
+/// 自动破解时尝试的古典密码及其密钥
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrackMethod {
+    /// 凯撒密码，携带偏移量（偏移量 13 归为 `Rot13`）
+    Caesar(u8),
+    /// ROT13
+    Rot13,
+    /// Atbash
+    Atbash,
+}
+
+impl fmt::Display for CrackMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrackMethod::Caesar(shift) => write!(f, "Caesar (shift {})", shift),
+            CrackMethod::Rot13 => write!(f, "ROT13"),
+            CrackMethod::Atbash => write!(f, "Atbash"),
+        }
+    }
+}
+
+/// 一个破解候选：使用的方法、解密结果及其卡方值
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrackCandidate {
+    pub method: CrackMethod,
+    pub plaintext: String,
+    pub score: f64,
+}
+
+/// 在不知道具体算法时，尝试所有常见的无密钥/弱密钥古典密码
+///
+/// 依次尝试凯撒密码的全部 26 种偏移量（偏移量 13 即 ROT13）以及 Atbash，
+/// 用 [`english_score`] 打分后按卡方值从小到大排序返回，第一个即为最佳候选。
+///
+/// # 示例
+///
+/// ```
+/// use ciphery::analysis::{crack, CrackMethod};
+/// let best = &crack("Gsv jfrxp yildm ulc qfnkh levi gsv ozab wlt")[0];
+/// assert_eq!(best.method, CrackMethod::Atbash);
+/// ```
+pub fn crack(text: &str) -> Vec<CrackCandidate> {
+    let mut candidates: Vec<CrackCandidate> = caesar::brute_force(text)
+        .into_iter()
+        .map(|(shift, plaintext)| CrackCandidate {
+            method: if shift == 13 {
+                CrackMethod::Rot13
+            } else {
+                CrackMethod::Caesar(shift)
+            },
+            score: english_score(&plaintext),
+            plaintext,
+        })
+        .collect();
+
+    let plaintext = atbash::transform(text);
+    candidates.push(CrackCandidate {
+        method: CrackMethod::Atbash,
+        score: english_score(&plaintext),
+        plaintext,
+    });
+
+    candidates.sort_by(|a, b| a.score.total_cmp(&b.score));
+    candidates
+}
+
+/// 单字节 XOR 候选中每个"不像正文"的字符（非 ASCII、罕见符号）所加的罚分
+const NON_TEXT_PENALTY: f64 = 10.0;
+
+/// 破解单字节密钥的 XOR（CTF 中的常见题型）
+///
+/// `hex` 为 hex 编码的密文（首尾空白会被忽略）。依次尝试全部 256 个单字节密钥：
+/// 解密结果不是合法 UTF-8、或含有换行/制表符以外的控制字符的候选直接跳过，
+/// 其余候选用 [`english_score`] 打分，并对每个非 ASCII 字符或罕见符号追加罚分，
+/// 返回得分最低（最像英文）的 `(密钥, 明文)`。
+///
+/// 输入不是合法 hex、或没有任何候选是可读文本时返回 `None`。
+///
+/// # 示例
+///
+/// ```
+/// use ciphery::analysis::crack_single_byte_xor;
+/// let (key, plaintext) = crack_single_byte_xor("5e424f0a4f4b4d464f0a424b590a464b444e4f4e").unwrap();
+/// assert_eq!((key, plaintext.as_str()), (0x2a, "the eagle has landed"));
+/// ```
+pub fn crack_single_byte_xor(hex: &str) -> Option<(u8, String)> {
+    let bytes = hex::decode(hex.trim()).ok()?;
+    (0..=u8::MAX)
+        .filter_map(|key| {
+            let plaintext = String::from_utf8(bytes.iter().map(|b| b ^ key).collect()).ok()?;
+            if plaintext
+                .chars()
+                .any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+            {
+                return None;
+            }
+            let unusual = plaintext
+                .chars()
+                .filter(|c| {
+                    !(c.is_ascii_alphanumeric()
+                        || c.is_ascii_whitespace()
+                        || matches!(c, '.' | ',' | '\'' | '"' | '!' | '?' | '-' | ':' | ';'))
+                })
+                .count();
+            let score = english_score(&plaintext) + NON_TEXT_PENALTY * unusual as f64;
+            Some((key, plaintext, score))
+        })
+        .min_by(|a, b| a.2.total_cmp(&b.2))
+        .map(|(key, plaintext, _)| (key, plaintext))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english_scores_lower_than_gibberish() {
+        let english = "It was the best of times, it was the worst of times";
+        let gibberish = "Qx zqj xjk vqzx jq xzqkj, qx zqj xjk zjkvx jq xzqkj";
+        assert!(english_score(english) < english_score(gibberish));
+    }
+
+    #[test]
+    fn test_english_score_ignores_case_and_punctuation() {
+        assert_eq!(english_score("Hello, World!"), english_score("helloworld"));
+    }
+
+    #[test]
+    fn test_english_score_without_letters() {
+        assert_eq!(english_score("123 !?"), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_letter_frequency_counts() {
+        let counts = letter_frequency("aaab");
+        assert_eq!(counts[0], 3);
+        assert_eq!(counts[1], 1);
+        assert_eq!(counts.iter().sum::<u32>(), 4);
+
+        // 忽略大小写与非字母字符
+        assert_eq!(letter_frequency("A a, 1 世 A!B"), letter_frequency("aaab"));
+    }
+
+    #[test]
+    fn test_crack_identifies_atbash() {
+        let plaintext = "Meet me near the old bridge after sunset and bring the documents";
+        let best = &crack(&atbash::transform(plaintext))[0];
+        assert_eq!(best.method, CrackMethod::Atbash);
+        assert_eq!(best.plaintext, plaintext);
+    }
+
+    #[test]
+    fn test_crack_identifies_caesar_and_rot13() {
+        let plaintext = "Meet me near the old bridge after sunset and bring the documents";
+
+        let best = &crack(&caesar::encrypt(plaintext, 7))[0];
+        assert_eq!(best.method, CrackMethod::Caesar(7));
+        assert_eq!(best.plaintext, plaintext);
+
+        let best = &crack(&caesar::encrypt(plaintext, 13))[0];
+        assert_eq!(best.method, CrackMethod::Rot13);
+    }
+
+    #[test]
+    fn test_crack_single_byte_xor_recovers_key() {
+        let plaintext = "Cooking MC's like a pound of bacon";
+        let ciphertext = hex::encode(plaintext.bytes().map(|b| b ^ 0x58).collect::<Vec<u8>>());
+        assert_eq!(
+            ciphertext,
+            "1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736"
+        );
+        assert_eq!(
+            crack_single_byte_xor(&ciphertext),
+            Some((0x58, plaintext.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_crack_single_byte_xor_rejects_bad_input() {
+        assert_eq!(crack_single_byte_xor("not hex"), None);
+        assert_eq!(crack_single_byte_xor("abc"), None);
+    }
+}
@@ -0,0 +1,145 @@
+//! "装甲"(armor) 格式：把密文包装成带标签的文本块，便于分享
+//!
+//! 格式仿照 PGP 的 ASCII armor，头部记录所用算法（**不包含密钥**），
+//! 接收方据此即可知道该用哪种算法解密：
+//!
+//! ```text
+//! -----BEGIN CIPHERY MESSAGE-----
+//! algo: caesar
+//!
+//! Khoor, Zruog!
+//! -----END CIPHERY MESSAGE-----
+//! ```
+//!
+//! 头部由若干 `名称: 值` 行组成，以一个空行结束；目前只识别 `algo`，其余头部行会被忽略。
+//!
+//! ```
+//! use ciphery::armor;
+//!
+//! let block = armor::armor("caesar", "Khoor");
+//! let armored = armor::dearmor(&block).unwrap();
+//! assert_eq!(armored.algo, "caesar");
+//! assert_eq!(armored.body, "Khoor");
+//! ```
+
+use crate::CipherError;
+
+/// 起始行
+pub const BEGIN: &str = "-----BEGIN CIPHERY MESSAGE-----";
+
+/// 结束行
+pub const END: &str = "-----END CIPHERY MESSAGE-----";
+
+/// 从装甲文本块中解析出的内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Armored {
+    /// 头部记录的算法名
+    pub algo: String,
+    /// 密文正文
+    pub body: String,
+}
+
+/// 把密文包装成装甲文本块，头部记录算法名 `algo`
+pub fn armor(algo: &str, body: &str) -> String {
+    format!("{}\nalgo: {}\n\n{}\n{}", BEGIN, algo, body, END)
+}
+
+/// 文本是否为装甲文本块（忽略首尾空白后以起始行开头）
+pub fn is_armored(text: &str) -> bool {
+    text.trim_start().starts_with(BEGIN)
+}
+
+/// 解析装甲文本块
+///
+/// 缺少起始行/结束行、头部没有以空行结束或缺少 `algo` 时返回 `CipherError::InvalidInput`。
+/// 正文保留原有的换行，只去掉首尾的空行。
+pub fn dearmor(text: &str) -> Result<Armored, CipherError> {
+    let mut lines = text.trim().lines();
+    if lines.next().map(str::trim) != Some(BEGIN) {
+        return Err(invalid("missing BEGIN line"));
+    }
+
+    let mut algo = None;
+    loop {
+        let line = lines
+            .next()
+            .ok_or_else(|| invalid("header must be followed by an empty line"))?;
+        let line = line.trim();
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| invalid(&format!("malformed header line '{}'", line)))?;
+        if name.trim().eq_ignore_ascii_case("algo") {
+            algo = Some(value.trim().to_string());
+        }
+    }
+    let algo = algo
+        .filter(|algo| !algo.is_empty())
+        .ok_or_else(|| invalid("missing 'algo' header"))?;
+
+    let mut body = Vec::new();
+    for line in lines.by_ref() {
+        if line.trim() == END {
+            return Ok(Armored {
+                algo,
+                body: body.join("\n").trim_matches('\n').to_string(),
+            });
+        }
+        body.push(line);
+    }
+    Err(invalid("missing END line"))
+}
+
+fn invalid(reason: &str) -> CipherError {
+    CipherError::InvalidInput(format!("invalid armored message: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_armor_roundtrip() {
+        let block = armor("rail-fence", "first line\nsecond line");
+        assert_eq!(
+            block,
+            "-----BEGIN CIPHERY MESSAGE-----\nalgo: rail-fence\n\nfirst line\nsecond line\n-----END CIPHERY MESSAGE-----"
+        );
+        assert!(is_armored(&block));
+        assert_eq!(
+            dearmor(&format!("\n  {}\n\n", block)).unwrap(),
+            Armored {
+                algo: "rail-fence".to_string(),
+                body: "first line\nsecond line".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_dearmor_ignores_unknown_headers() {
+        let block = format!("{}\nAlgo: xor\nversion: 1\n\n0a1b\n{}", BEGIN, END);
+        let armored = dearmor(&block).unwrap();
+        assert_eq!(armored.algo, "xor");
+        assert_eq!(armored.body, "0a1b");
+    }
+
+    #[test]
+    fn test_dearmor_rejects_malformed_blocks() {
+        for block in [
+            "Khoor".to_string(),
+            format!("{}\nalgo: caesar\n\nKhoor", BEGIN),
+            format!("{}\n\nKhoor\n{}", BEGIN, END),
+            format!("{}\nalgo: caesar\nKhoor\n{}", BEGIN, END),
+            format!("{}\nalgo:\n\nKhoor\n{}", BEGIN, END),
+        ] {
+            assert!(
+                matches!(dearmor(&block), Err(CipherError::InvalidInput(_))),
+                "{}",
+                block
+            );
+        }
+        assert!(!is_armored("Khoor"));
+    }
+}
@@ -0,0 +1,89 @@
+//! Atbash 密码的实现
+//!
+//! Atbash 将字母表反转映射（A↔Z、B↔Y ……），没有密钥。
+//! 由于映射是对称的，加密与解密是同一个操作（自反密码）。
+
+use crate::{Cipher, CipherError, KeyRequirement, ensure_alphabetic};
+
+/// Atbash 密码（无密钥）
+#[derive(Debug, Clone, Default)]
+pub struct Atbash {
+    /// 严格模式：遇到非字母字符时报错，而非原样保留
+    strict: bool,
+}
+
+impl Atbash {
+    /// 创建一个新的 Atbash 密码实例
+    pub fn new() -> Self {
+        Self { strict: false }
+    }
+
+    /// 设置严格模式：为 `true` 时，输入中出现任何非 ASCII 字母字符都会返回 `CipherError::InvalidInput`
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+}
+
+/// Atbash 变换函数：大小写分别反转，非 ASCII 字母保持不变
+///
+/// # 示例
+///
+/// ```
+/// use ciphery::atbash::transform;
+/// assert_eq!(transform("Hello, World!"), "Svool, Dliow!");
+/// ```
+pub fn transform(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'a'..='z' => (b'z' - (c as u8 - b'a')) as char,
+            'A'..='Z' => (b'Z' - (c as u8 - b'A')) as char,
+            _ => c,
+        })
+        .collect()
+}
+
+impl Cipher for Atbash {
+    fn encrypt(&self, text: &str) -> Result<String, CipherError> {
+        if self.strict {
+            ensure_alphabetic(text)?;
+        }
+        Ok(transform(text))
+    }
+
+    fn decrypt(&self, text: &str) -> Result<String, CipherError> {
+        // 自反密码：解密就是再加密一次
+        self.encrypt(text)
+    }
+
+    fn inverse(&self) -> Box<dyn Cipher> {
+        Box::new(self.clone())
+    }
+
+    fn key_requirement(&self) -> KeyRequirement {
+        KeyRequirement::None
+    }
+
+    fn is_reciprocal(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atbash_encrypt() {
+        let cipher = Atbash::new();
+        assert_eq!(cipher.encrypt("abcxyz ABCXYZ").unwrap(), "zyxcba ZYXCBA");
+    }
+
+    #[test]
+    fn test_atbash_is_reciprocal() {
+        let cipher = Atbash::new();
+        let text = "Attack at dawn! 世界";
+        let encrypted = cipher.encrypt(text).unwrap();
+        assert_eq!(cipher.encrypt(&encrypted).unwrap(), text);
+    }
+}
@@ -0,0 +1,141 @@
+//! Beaufort 密码的实现
+//!
+//! Beaufort 是 Vigenere 的一种变体，加密公式为 `C = (K - P) mod 26`。
+//! 由于 `P = (K - C) mod 26` 形式完全相同，它是"自反"(reciprocal) 的：加密与解密是同一个操作。
+
+// 引入核心 Trait 和错误类型
+use crate::vigenere::parse_key;
+use crate::{Cipher, CipherError, KeyRequirement, Redacted, ensure_alphabetic};
+use std::fmt;
+
+#[derive(Clone)]
+pub struct Beaufort {
+    key: Vec<u8>,            // 存储大写密钥的字节数组，方便按索引访问
+    strict: bool,            // 严格模式：遇到非字母字符时报错，而非原样保留
+    count_non_letters: bool, // 非字母字符是否也推进密钥位置
+}
+
+impl Beaufort {
+    /// 创建一个新的 Beaufort 密码实例
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 密钥，规则与 Vigenere 相同（非空，仅含 ASCII 字母），否则返回 `CipherError::InvalidKey`
+    pub fn new(key: &str) -> Result<Self, CipherError> {
+        Ok(Self {
+            key: parse_key(key)?,
+            strict: false,
+            count_non_letters: false,
+        })
+    }
+
+    /// 设置严格模式：为 `true` 时，输入中出现任何非 ASCII 字母字符都会返回 `CipherError::InvalidInput`
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// 设置非字母字符是否推进密钥位置，含义与 [`Vigenere::with_count_non_letters`] 相同
+    ///
+    /// [`Vigenere::with_count_non_letters`]: crate::vigenere::Vigenere::with_count_non_letters
+    pub fn with_count_non_letters(mut self, count_non_letters: bool) -> Self {
+        self.count_non_letters = count_non_letters;
+        self
+    }
+
+    /// 对文本执行 Beaufort 变换（加密与解密共用）
+    fn transform(&self, text: &str) -> String {
+        let mut key_index = 0;
+        text.chars()
+            .map(|c| {
+                if c.is_ascii_alphabetic() {
+                    let base = if c.is_ascii_uppercase() { b'A' } else { b'a' };
+                    let k = self.key[key_index % self.key.len()] - b'A';
+                    key_index += 1; // 只有当遇到字母时才增加密钥索引
+                    ((k + 26 - (c as u8 - base)) % 26 + base) as char
+                } else {
+                    if self.count_non_letters {
+                        key_index += 1;
+                    }
+                    c // 非字母字符直接返回，不加密
+                }
+            })
+            .collect()
+    }
+}
+
+impl fmt::Debug for Beaufort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Beaufort")
+            .field(
+                "key",
+                &Redacted {
+                    len: Some(self.key.len()),
+                },
+            )
+            .field("strict", &self.strict)
+            .field("count_non_letters", &self.count_non_letters)
+            .finish()
+    }
+}
+
+impl Cipher for Beaufort {
+    fn encrypt(&self, text: &str) -> Result<String, CipherError> {
+        if self.strict {
+            ensure_alphabetic(text)?;
+        }
+        Ok(self.transform(text))
+    }
+
+    fn decrypt(&self, text: &str) -> Result<String, CipherError> {
+        // 自反密码：解密就是再加密一次
+        self.encrypt(text)
+    }
+
+    fn inverse(&self) -> Box<dyn Cipher> {
+        Box::new(self.clone())
+    }
+
+    fn key_requirement(&self) -> KeyRequirement {
+        KeyRequirement::Keyword
+    }
+
+    fn is_reciprocal(&self) -> bool {
+        true
+    }
+
+    fn rekey(&self, new_key: &str) -> Result<Box<dyn Cipher>, CipherError> {
+        Ok(Box::new(Self {
+            key: parse_key(new_key)?,
+            ..self.clone()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_beaufort_encrypt() {
+        let cipher = Beaufort::new("FORTIFICATION").unwrap();
+        let encrypted = cipher.encrypt("DEFENDTHEEASTWALL").unwrap();
+        assert_eq!(encrypted, "CKMPVCPVWPIWUJOGI");
+    }
+
+    #[test]
+    fn test_beaufort_is_reciprocal() {
+        let cipher = Beaufort::new("FORTIFICATION").unwrap();
+        let text = "Defend the east wall!";
+        let encrypted = cipher.encrypt(text).unwrap();
+        assert_eq!(cipher.encrypt(&encrypted).unwrap(), text);
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), text);
+    }
+
+    #[test]
+    fn test_beaufort_passes_non_letters_through() {
+        let cipher = Beaufort::new("KEY").unwrap();
+        let encrypted = cipher.encrypt("a b, 世界!").unwrap();
+        assert_eq!(encrypted, "k d, 世界!");
+    }
+}
@@ -1,6 +1,15 @@
 //! 凯撒密码 (Caesar Cipher) 的实现
 //!
 //! 凯撒密码是一种简单的替换加密技术，通过将字母表中的每个字母移动固定数量的位置来进行加密。
+//!
+//! 本模块提供两套等价的接口：
+//!
+//! * [`encrypt`] / [`decrypt`] 直接返回 `String`，适合只想快速变换一段文本的简单调用方；
+//! * [`try_encrypt`] / [`try_decrypt`] 返回 `Result<String, CipherError>`，与 [`Caesar`] 的
+//!   [`Cipher`] 实现以及其他算法的签名保持一致，`Caesar` 内部也经由它们完成变换。
+//!
+//! 偏移量总是对 26 取模，两套接口目前都不会失败，输出也完全相同；
+//! 需要统一处理错误（例如与其他算法混用）的代码应优先使用 `try_*` 版本。
 
 /// 凯撒密码加密函数
 ///
@@ -76,16 +85,151 @@ pub fn decrypt(text: &str, shift: u8) -> String {
     encrypt(text, reverse_shift)
 }
 
+/// 凯撒密码加密函数的 `Result` 版本
+///
+/// 与 [`encrypt`] 输出完全相同，但签名与 [`Cipher::encrypt`] 一致，便于与其他算法统一处理错误。
+/// 偏移量对 26 取模，因此目前总是返回 `Ok`。
+///
+/// # 示例
+///
+/// ```
+/// use ciphery::caesar::try_encrypt;
+/// assert_eq!(try_encrypt("hello", 3).unwrap(), "khoor");
+/// ```
+pub fn try_encrypt(text: &str, shift: u8) -> Result<String, CipherError> {
+    Ok(encrypt(text, shift))
+}
+
+/// 凯撒密码解密函数的 `Result` 版本，与 [`decrypt`] 输出完全相同
+///
+/// # 示例
+///
+/// ```
+/// use ciphery::caesar::try_decrypt;
+/// assert_eq!(try_decrypt("khoor", 3).unwrap(), "hello");
+/// ```
+pub fn try_decrypt(text: &str, shift: u8) -> Result<String, CipherError> {
+    Ok(decrypt(text, shift))
+}
+
+/// "每日密码"的偏移量：日期是一年中的第几天（1 月 1 日为 1）对 26 取模
+///
+/// 同一天加密的消息当天即可解密，双方无需交换密钥（当然也谈不上安全，只是一个小玩具）。
+///
+/// # 示例
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use ciphery::caesar::daily_shift;
+/// // 3 月 1 日是 2024 年的第 61 天，61 mod 26 = 9
+/// assert_eq!(daily_shift(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()), 9);
+/// ```
+#[cfg(feature = "daily")]
+pub fn daily_shift(date: chrono::NaiveDate) -> u8 {
+    use chrono::Datelike;
+    (date.ordinal() % 26) as u8
+}
+
+/// 解析凯撒密码的密钥字符串，返回未取模的偏移量
+///
+/// 除非负整数外，也接受单个字母，按 A=0、B=1 …… Z=25 换算（不区分大小写），例如 `"D"` 与 `"3"` 等价。
+/// 注意部分教材采用 A=1 的约定，此时 `D` 对应的是偏移量 4。调用方按各自的字母表长度取模。
+/// 命令行、[`Cipher::rekey`] 与算法注册表共用这一规则，同一个密钥在各处的含义相同。
+///
+/// # 示例
+///
+/// ```
+/// use ciphery::caesar::parse_key;
+/// assert_eq!(parse_key("d").unwrap(), 3);
+/// assert_eq!(parse_key("29").unwrap(), 29);
+/// assert!(parse_key("DE").is_err());
+/// ```
+pub fn parse_key(key: &str) -> Result<u64, CipherError> {
+    if let [letter] = key.as_bytes()
+        && letter.is_ascii_alphabetic()
+    {
+        return Ok(u64::from(letter.to_ascii_uppercase() - b'A'));
+    }
+    key.parse().map_err(|_| {
+        CipherError::InvalidKey(
+            "Caesar key must be a number or a single letter (A=0 ... Z=25)".to_string(),
+        )
+    })
+}
+
+/// 暴力破解：尝试全部 26 种偏移量进行解密
+///
+/// 返回 `(偏移量, 解密结果)` 列表，按偏移量 0..26 排列。
+///
+/// # 示例
+///
+/// ```
+/// use ciphery::caesar::brute_force;
+/// let candidates = brute_force("khoor");
+/// assert_eq!(candidates.len(), 26);
+/// assert_eq!(candidates[3], (3, "hello".to_string()));
+/// ```
+pub fn brute_force(text: &str) -> Vec<(u8, String)> {
+    (0..26).map(|shift| (shift, decrypt(text, shift))).collect()
+}
+
+/// 暴力破解并只保留最"像英文"的 `k` 个候选结果
+///
+/// 使用 [`english_score`] 对每个候选打分，按卡方值从小到大排序（越小越像英文）。
+/// 返回 `(偏移量, 解密结果, 卡方值)` 列表，长度为 `min(k, 26)`。
+///
+/// # 示例
+///
+/// ```
+/// use ciphery::caesar::top_candidates;
+/// let top = top_candidates("Wkh txlfn eurzq ira mxpsv ryhu wkh odcb grj", 1);
+/// assert_eq!(top[0].0, 3);
+/// ```
+pub fn top_candidates(text: &str, k: usize) -> Vec<(u8, String, f64)> {
+    let mut scored: Vec<(u8, String, f64)> = brute_force(text)
+        .into_iter()
+        .map(|(shift, candidate)| {
+            let score = english_score(&candidate);
+            (shift, candidate, score)
+        })
+        .collect();
+
+    // 卡方值可能为 INFINITY（没有字母），使用 total_cmp 保证排序稳定可靠
+    scored.sort_by(|a, b| a.2.total_cmp(&b.2));
+    scored.truncate(k);
+    scored
+}
+
 // 引入核心 Trait 和错误类型
-use crate::{Cipher, CipherError};
+use crate::analysis::english_score;
+use crate::{
+    Cipher, CipherError, Inverse, KeyRequirement, Redacted, ensure_alphabetic, ensure_safe_charset,
+};
+use std::fmt;
 
 /// 凯撒密码结构体
 ///
 /// 这是一个持有状态（偏移量）的结构体，它实现了 `Cipher` Trait。
 /// 这种设计模式允许我们将不同的加密算法统一抽象为 `Cipher` 对象。
+///
+/// 构造时会预先计算加密/解密两张 256 项的字节映射表：纯 ASCII 输入只需逐字节查表一遍，
+/// 省去逐字符的 `match` 与取模运算；含非 ASCII 字符时回退到基于 `char` 的实现，结果完全一致。
+#[derive(Clone)]
 pub struct Caesar {
     /// 凯撒密码的偏移量
     shift: u8,
+    /// 严格模式：遇到非字母字符时报错，而非原样保留
+    strict: bool,
+    /// 文件名安全模式：数字同样参与平移，只接受 `[A-Za-z0-9._-]`
+    safe_charset: bool,
+    /// 可打印 ASCII 模式：在 95 个可打印字符（空格到 `~`）上循环平移，而不仅是字母
+    printable: bool,
+    /// ROT13：偏移量固定为 13，不接受密钥
+    fixed: bool,
+    /// 加密用的字节映射表
+    encrypt_table: [u8; 256],
+    /// 解密用的字节映射表
+    decrypt_table: [u8; 256],
 }
 
 impl Caesar {
@@ -95,22 +239,245 @@ impl Caesar {
     ///
     /// * `shift` - 偏移量，会自动对 26 取模
     pub fn new(shift: u8) -> Self {
-        Self { shift: shift % 26 }
+        let shift = shift % 26;
+        Self {
+            shift,
+            strict: false,
+            safe_charset: false,
+            printable: false,
+            fixed: false,
+            encrypt_table: build_table(shift),
+            decrypt_table: build_table((26 - shift) % 26),
+        }
+    }
+
+    /// 创建一个 ROT13 实例：偏移量固定为 13，没有密钥
+    ///
+    /// 加解密结果与 `Caesar::new(13)` 完全相同，区别在于 [`Cipher::key_requirement`] 返回
+    /// `KeyRequirement::None`，[`Cipher::rekey`] 也会像其他无密钥算法一样报错。
+    pub fn rot13() -> Self {
+        Self {
+            fixed: true,
+            ..Self::new(13)
+        }
+    }
+
+    /// 创建一个在全部 95 个可打印 ASCII 字符（空格 `0x20` 到 `~` `0x7E`）上循环平移的凯撒密码实例
+    ///
+    /// 适合密码、令牌这类标点与数字同样重要的文本：字母、数字、标点和空格都会被平移，
+    /// 控制字符与非 ASCII 字符（如中文）原样保留。该模式下 [`Caesar::with_safe_charset`] 不生效。
+    ///
+    /// # 参数
+    ///
+    /// * `shift` - 偏移量，会自动对 95 取模
+    ///
+    /// # 示例
+    ///
+    /// ```
+    /// use ciphery::{Cipher, caesar::Caesar};
+    /// let cipher = Caesar::printable_ascii(1);
+    /// assert_eq!(cipher.encrypt("a1~").unwrap(), "b2 ");
+    /// ```
+    pub fn printable_ascii(shift: u8) -> Self {
+        let shift = shift % PRINTABLE_LEN;
+        Self {
+            shift,
+            strict: false,
+            safe_charset: false,
+            printable: true,
+            fixed: false,
+            encrypt_table: build_printable_table(shift),
+            decrypt_table: build_printable_table((PRINTABLE_LEN - shift) % PRINTABLE_LEN),
+        }
+    }
+
+    /// 创建一个"每日密码"实例，偏移量由本地时区的今天推导，见 [`daily_shift`]
+    #[cfg(feature = "daily")]
+    pub fn daily() -> Self {
+        Self::for_date(chrono::Local::now().date_naive())
+    }
+
+    /// 创建一个指定日期的"每日密码"实例，便于复现或解密以前的消息，见 [`daily_shift`]
+    #[cfg(feature = "daily")]
+    pub fn for_date(date: chrono::NaiveDate) -> Self {
+        Self::new(daily_shift(date))
+    }
+
+    /// 设置严格模式：为 `true` 时，输入中出现任何非 ASCII 字母字符（包括空格、标点）
+    /// 都会返回 `CipherError::InvalidInput`；默认为 `false`，即原样保留这些字符
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// 设置文件名安全模式，用于确定性地混淆文件名这类短标签
+    ///
+    /// 为 `true` 时字母照常平移，数字按 `shift mod 10` 在 `0-9` 内循环平移，`.`、`_`、`-` 原样保留；
+    /// 其他字符一律返回 `CipherError::InvalidInput`，因此合法文件名的输出依然是合法文件名。
+    pub fn with_safe_charset(mut self, safe_charset: bool) -> Self {
+        self.safe_charset = safe_charset;
+        self
     }
 }
 
+// 手动实现 Debug：偏移量即密钥，需要隐藏；映射表由偏移量推导而来，同样不输出
+impl fmt::Debug for Caesar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Caesar")
+            .field("shift", &Redacted { len: None })
+            .field("strict", &self.strict)
+            .field("safe_charset", &self.safe_charset)
+            .field("printable", &self.printable)
+            .finish()
+    }
+}
+
+/// 生成按 `shift` 平移字母的字节映射表，非字母字节映射为自身
+fn build_table(shift: u8) -> [u8; 256] {
+    let mut table = [0u8; 256];
+    for (byte, slot) in (0..=255u8).zip(table.iter_mut()) {
+        *slot = match byte {
+            b'a'..=b'z' => b'a' + (byte - b'a' + shift) % 26,
+            b'A'..=b'Z' => b'A' + (byte - b'A' + shift) % 26,
+            _ => byte,
+        };
+    }
+    table
+}
+
+/// 可打印 ASCII 字符（空格到 `~`）的个数
+pub(crate) const PRINTABLE_LEN: u8 = 95;
+
+/// 生成在可打印 ASCII 字符上循环平移 `shift` 位的字节映射表，其余字节映射为自身
+fn build_printable_table(shift: u8) -> [u8; 256] {
+    let mut table = [0u8; 256];
+    for (byte, slot) in (0..=255u8).zip(table.iter_mut()) {
+        *slot = match byte {
+            b' '..=b'~' => b' ' + (byte - b' ' + shift) % PRINTABLE_LEN,
+            _ => byte,
+        };
+    }
+    table
+}
+
+/// 逐字符查表变换文本：ASCII 字符按映射表替换，非 ASCII 字符原样保留
+fn map_ascii_chars(text: &str, table: &[u8; 256]) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_ascii() {
+                table[c as usize] as char
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// 使用映射表变换文本：纯 ASCII 时逐字节查表，否则回退到基于 `char` 的 [`try_encrypt`]
+fn apply_table(text: &str, table: &[u8; 256], shift: u8) -> Result<String, CipherError> {
+    if !text.is_ascii() {
+        return try_encrypt(text, shift);
+    }
+    let bytes: Vec<u8> = text.bytes().map(|b| table[b as usize]).collect();
+    // ASCII 字节经映射后仍是 ASCII，必然是合法的 UTF-8
+    Ok(String::from_utf8(bytes).expect("ASCII input always maps to ASCII output"))
+}
+
+/// 将 ASCII 数字在 `0-9` 内循环平移 `shift` 位（`shift` 取值 0..10），其余字符保持不变
+fn shift_digits(text: &str, shift: u8) -> String {
+    text.chars()
+        .map(|c| match c {
+            '0'..='9' => (b'0' + (c as u8 - b'0' + shift) % 10) as char,
+            _ => c,
+        })
+        .collect()
+}
+
 // 为 Caesar 结构体实现 Cipher Trait
 // 这是 Rust 中实现多态和接口抽象的核心机制
 impl Cipher for Caesar {
     fn encrypt(&self, text: &str) -> Result<String, CipherError> {
         // 凯撒密码的加密过程不会失败，因此我们直接调用底层函数并用 Ok 包装
         // 在更复杂的算法（如 AES）中，这里可能会返回 Err(CipherError::InvalidInput(...))
-        Ok(encrypt(text, self.shift))
+        // 唯一的例外是严格模式下遇到非字母字符
+        if self.strict {
+            ensure_alphabetic(text)?;
+        }
+        if self.printable {
+            return Ok(map_ascii_chars(text, &self.encrypt_table));
+        }
+        if self.safe_charset {
+            ensure_safe_charset(text)?;
+            let shifted = apply_table(text, &self.encrypt_table, self.shift)?;
+            return Ok(shift_digits(&shifted, self.shift % 10));
+        }
+        apply_table(text, &self.encrypt_table, self.shift)
     }
 
     fn decrypt(&self, text: &str) -> Result<String, CipherError> {
         // 同理，解密过程也不会失败
-        Ok(decrypt(text, self.shift))
+        if self.strict {
+            ensure_alphabetic(text)?;
+        }
+        if self.printable {
+            return Ok(map_ascii_chars(text, &self.decrypt_table));
+        }
+        if self.safe_charset {
+            ensure_safe_charset(text)?;
+            let shifted = apply_table(text, &self.decrypt_table, (26 - self.shift) % 26)?;
+            return Ok(shift_digits(&shifted, (10 - self.shift % 10) % 10));
+        }
+        apply_table(text, &self.decrypt_table, (26 - self.shift) % 26)
+    }
+
+    fn inverse(&self) -> Box<dyn Cipher> {
+        // 安全模式下数字按 mod 10 平移，反向偏移 26 - shift 无法还原数字，直接包装
+        if self.safe_charset && !self.printable {
+            return Box::new(Inverse(self.clone()));
+        }
+        if self.printable {
+            return Box::new(
+                Caesar::printable_ascii(PRINTABLE_LEN - self.shift).with_strict(self.strict),
+            );
+        }
+        // ROT13 自反，逆算法仍是（无密钥的）ROT13
+        if self.fixed {
+            return Box::new(self.clone());
+        }
+        // 凯撒密码的逆就是反向偏移，new 内部会对 26 取模（shift 为 0 时依然正确）
+        Box::new(Caesar::new(26 - self.shift).with_strict(self.strict))
+    }
+
+    fn key_requirement(&self) -> KeyRequirement {
+        if self.fixed {
+            KeyRequirement::None
+        } else {
+            KeyRequirement::NumericShift
+        }
+    }
+
+    fn rekey(&self, new_key: &str) -> Result<Box<dyn Cipher>, CipherError> {
+        if self.fixed {
+            return Err(CipherError::InvalidKey(
+                "this cipher does not take a key".to_string(),
+            ));
+        }
+        let shift = parse_key(new_key)?;
+        let mut cipher = if self.printable {
+            Caesar::printable_ascii((shift % PRINTABLE_LEN as u64) as u8)
+        } else {
+            Caesar::new((shift % 26) as u8)
+        };
+        cipher.strict = self.strict;
+        cipher.safe_charset = self.safe_charset;
+        Ok(Box::new(cipher))
+    }
+
+    fn is_reciprocal(&self) -> bool {
+        // 只有偏移量为 13（ROT13）或 0（恒等变换）时，加密两次才能还原；
+        // 安全模式下 ROT13 的数字平移 3 位，并不自反
+        // 可打印 ASCII 模式的字符集大小为 95（奇数），只有偏移量为 0 时才自反
+        (self.shift == 13 && !self.safe_charset && !self.printable) || self.shift == 0
     }
 }
 
@@ -190,4 +557,190 @@ mod tests {
         let decrypted = cipher.encrypt(&encrypted).unwrap();
         assert_eq!(decrypted, "hello");
     }
+
+    #[test]
+    fn test_rot13_is_keyless() {
+        let cipher = Caesar::rot13().with_strict(true);
+        assert_eq!(cipher.encrypt("hello").unwrap(), "uryyb");
+        assert_eq!(cipher.key_requirement(), KeyRequirement::None);
+        assert_eq!(cipher.inverse().key_requirement(), KeyRequirement::None);
+        assert!(cipher.inverse().encrypt("hello world").is_err());
+        assert!(cipher.rekey("3").is_err());
+        assert_eq!(
+            Caesar::new(13).key_requirement(),
+            KeyRequirement::NumericShift
+        );
+    }
+
+    #[test]
+    fn test_lookup_table_matches_char_path() {
+        // 大段 ASCII 与夹杂 Unicode 的文本分别走查表路径与回退路径，结果都应与逐字符实现一致
+        let ascii =
+            "The quick brown fox jumps over the lazy dog. 0123456789 ~!@#$%^&*()\n".repeat(500);
+        let mixed = format!("{}Hello 世界 🦀 Ünïcödé{}", ascii, ascii);
+        for shift in 0..26 {
+            let cipher = Caesar::new(shift);
+            for text in [&ascii, &mixed] {
+                let encrypted = cipher.encrypt(text).unwrap();
+                assert_eq!(encrypted, encrypt(text, shift));
+                assert_eq!(
+                    cipher.decrypt(&encrypted).unwrap(),
+                    decrypt(&encrypted, shift)
+                );
+                assert_eq!(cipher.decrypt(&encrypted).unwrap(), *text);
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_functions_match_infallible_ones() {
+        let text = "Hello, World! 你好 123";
+        for shift in [0, 3, 13, 25, 26, 200, 255] {
+            assert_eq!(try_encrypt(text, shift).unwrap(), encrypt(text, shift));
+            assert_eq!(try_decrypt(text, shift).unwrap(), decrypt(text, shift));
+            assert_eq!(
+                Caesar::new(shift).encrypt(text).unwrap(),
+                try_encrypt(text, shift).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_caesar_strict_mode() {
+        let lenient = Caesar::new(3);
+        assert_eq!(lenient.encrypt("hello!").unwrap(), "khoor!");
+
+        let strict = Caesar::new(3).with_strict(true);
+        assert_eq!(
+            strict.encrypt("hello!"),
+            Err(CipherError::InvalidInput(
+                "non-alphabetic character '!' at position 5 (strict mode)".to_string()
+            ))
+        );
+        assert!(strict.decrypt("khoor!").is_err());
+        assert!(strict.inverse().encrypt("khoor!").is_err());
+        assert_eq!(strict.encrypt("hello").unwrap(), "khoor");
+    }
+
+    #[test]
+    fn test_caesar_safe_charset_keeps_filename_legal() {
+        let cipher = Caesar::new(3).with_safe_charset(true);
+        let encrypted = cipher.encrypt("report_2024.txt").unwrap();
+        assert_eq!(encrypted, "uhsruw_5357.waw");
+        assert!(
+            encrypted
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+        );
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), "report_2024.txt");
+        assert_eq!(
+            cipher.inverse().encrypt(&encrypted).unwrap(),
+            "report_2024.txt"
+        );
+
+        // 空格、路径分隔符等不在安全字符集内，直接报错
+        assert!(matches!(
+            cipher.encrypt("my report.txt"),
+            Err(CipherError::InvalidInput(_))
+        ));
+        assert!(cipher.encrypt("dir/file").is_err());
+    }
+
+    #[test]
+    fn test_caesar_printable_ascii_shifts_everything() {
+        let cipher = Caesar::printable_ascii(3);
+        let encrypted = cipher.encrypt("Pass@123").unwrap();
+        assert_eq!(encrypted, "SdvvC456");
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), "Pass@123");
+        assert_eq!(cipher.inverse().encrypt(&encrypted).unwrap(), "Pass@123");
+
+        // 在 `~` 之后回绕到空格；控制字符与非 ASCII 字符原样保留
+        assert_eq!(cipher.encrypt("~}|").unwrap(), "\"! ");
+        assert_eq!(cipher.encrypt("a\tb 世").unwrap(), "d\te#世");
+        assert!(!cipher.is_reciprocal());
+    }
+
+    #[cfg(feature = "daily")]
+    #[test]
+    fn test_caesar_daily_shift_for_fixed_date() {
+        use chrono::NaiveDate;
+
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        assert_eq!(daily_shift(date), 9);
+        // 第 26 天偏移量回到 0，第 365 天为 365 mod 26 = 1
+        assert_eq!(
+            daily_shift(NaiveDate::from_ymd_opt(2025, 1, 26).unwrap()),
+            0
+        );
+        assert_eq!(
+            daily_shift(NaiveDate::from_ymd_opt(2025, 12, 31).unwrap()),
+            1
+        );
+
+        let cipher = Caesar::for_date(date);
+        assert_eq!(cipher.encrypt("hello").unwrap(), encrypt("hello", 9));
+    }
+
+    #[test]
+    fn test_caesar_debug_redacts_shift() {
+        let debug = format!("{:?}", Caesar::new(7));
+        assert_eq!(
+            debug,
+            "Caesar { shift: <redacted>, strict: false, safe_charset: false, printable: false }"
+        );
+    }
+
+    #[test]
+    fn test_caesar_rekey_accepts_same_keys_as_cli() {
+        let cipher = Caesar::new(1);
+        for key in ["3", "D", "d", "29"] {
+            let rekeyed = cipher.rekey(key).unwrap();
+            assert_eq!(rekeyed.encrypt("hello").unwrap(), "khoor", "{}", key);
+        }
+        assert!(matches!(
+            cipher.rekey("DE"),
+            Err(CipherError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            cipher.rekey("-1"),
+            Err(CipherError::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_caesar_inverse() {
+        let cipher = Caesar::new(3);
+        let inverse = cipher.inverse();
+        assert_eq!(
+            inverse.encrypt("khoor").unwrap(),
+            cipher.decrypt("khoor").unwrap()
+        );
+        assert_eq!(inverse.decrypt("hello").unwrap(), "khoor");
+
+        // shift 为 0 时逆算法同样是恒等变换
+        assert_eq!(Caesar::new(0).inverse().encrypt("hello").unwrap(), "hello");
+    }
+
+    #[test]
+    fn test_brute_force_contains_all_shifts() {
+        let candidates = brute_force("khoor");
+        assert_eq!(candidates.len(), 26);
+        assert!(candidates.contains(&(3, "hello".to_string())));
+    }
+
+    #[test]
+    fn test_top_candidates_finds_correct_shift() {
+        let plaintext = "Meet me near the old bridge after sunset and bring the documents";
+        let ciphertext = encrypt(plaintext, 11);
+
+        let top = top_candidates(&ciphertext, 3);
+        assert_eq!(top.len(), 3);
+        assert!(
+            top.iter()
+                .any(|(shift, text, _)| *shift == 11 && text == plaintext)
+        );
+
+        // 结果按卡方值升序排列
+        assert!(top.windows(2).all(|w| w[0].2 <= w[1].2));
+    }
 }
@@ -3,7 +3,10 @@
 //! 本模块使用 `clap` 的 derive API 定义了所有的命令行参数结构、
 //! 子命令以及算法枚举，将 CLI 的"长什么样"与"做什么事"分离开来。
 
-use clap::{Parser, Subcommand, ValueEnum};
+use ciphery::columnar::{ReadOrder, WriteOrder};
+use ciphery::square::Merge;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::num::NonZeroUsize;
 
 // ============================================================================
 // CLI 元数据定义 (方便后续统一修改和扩展)
@@ -23,7 +26,7 @@ const CLI_LONG_ABOUT: &str = concat!(
     "  Demo    :  http://smlcoke.com\n",
     "══════════════════════════════════════════════════════════\n",
     "\n",
-    "Supports multiple algorithms: Caesar, ROT13, Vigenere, XOR, Rail Fence, Base64.\n",
+    "Supports multiple algorithms: Caesar, ROT13, Atbash, Vigenere, Beaufort, XOR, Rail Fence, Columnar, Playfair, Polybius, Morse, NATO, Reverse, Base64.\n",
     "Run without arguments to enter the interactive REPL mode.\n",
 );
 
@@ -79,7 +82,7 @@ pub struct Cli {
 // 子命令定义
 // ============================================================================
 
-/// 定义子命令枚举：Encrypt 和 Decrypt
+/// 定义子命令枚举：Encrypt、Decrypt、Crack、CrackXor、Freq 与 VigenereTable
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Perform encryption operation
@@ -92,13 +95,19 @@ pub enum Commands {
         #[arg(short, long, value_enum, default_value_t = Algorithm::Caesar)]
         algo: Algorithm,
 
-        /// 加密密钥 (对于凯撒密码，这是一个数字)
+        /// 加密密钥 (对于凯撒密码，这是一个数字，或一个按 A=0 换算的字母，如 D 即偏移量 3)
         #[arg(short, long)]
         key: Option<String>,
 
-        /// 待加密文本的文件路径
+        /// 待加密文本的文件路径（可重复传入多个，批量处理并写入 `<文件名>.enc`）
         #[arg(short, long)]
-        file_path: Option<String>,
+        file_path: Vec<String>,
+
+        #[command(flatten)]
+        run: RunOptions,
+
+        #[command(flatten)]
+        options: CipherOptions,
     },
 
     /// Perform decryption operation
@@ -115,10 +124,252 @@ pub enum Commands {
         #[arg(short, long)]
         key: Option<String>,
 
-        /// 待解密文本的文件路径
+        /// 待解密文本的文件路径（可重复传入多个，批量处理并写入 `<文件名>.dec`）
+        #[arg(short, long)]
+        file_path: Vec<String>,
+
+        #[command(flatten)]
+        run: RunOptions,
+
+        #[command(flatten)]
+        options: CipherOptions,
+
+        /// 暴力破解：尝试所有偏移量（仅支持 Caesar），无需提供密钥
+        #[arg(long)]
+        brute: bool,
+
+        /// 暴力破解时只显示最像英文的前 K 个结果
+        #[arg(long, value_name = "K", requires = "brute")]
+        top: Option<usize>,
+
+        /// 暴力破解时列出全部 26 个候选偏移量及其英文得分（按得分从好到坏排序），便于判断结果是否可疑
+        #[arg(long, requires = "brute", conflicts_with = "top")]
+        show_scores: bool,
+
+        /// 逐行解密：输入的每一行视为一段独立的密文（例如每行一条 XOR hex 记录）
+        #[arg(long, conflicts_with = "brute")]
+        lines: bool,
+
+        /// 逐行解密时某一行失败不中止：在 stderr 记录行号与错误，原样输出该行并继续，最后汇总失败行数
+        #[arg(long, requires = "lines")]
+        continue_on_error: bool,
+
+        /// 输入的密文按组书写（如 `encrypt --group 5` 的输出）：解密前去掉所有空白
+        #[arg(long, conflicts_with_all = ["brute", "lines"])]
+        grouped: bool,
+    },
+
+    /// Guess the classical cipher (Caesar / ROT13 / Atbash) and print the best decryption
+    Crack {
+        /// 要破解的密文
+        #[arg(short, long)]
+        text: Option<String>,
+
+        /// 待破解密文的文件路径
+        #[arg(short, long)]
+        file_path: Option<String>,
+
+        /// 除最佳结果外，列出所有尝试过的方法及其英文得分（按得分从好到坏排序）
+        #[arg(long)]
+        show_scores: bool,
+    },
+
+    /// Recover the key of single-byte XOR from hex ciphertext (tries all 256 keys)
+    CrackXor {
+        /// hex 编码的密文
+        #[arg(short, long)]
+        text: Option<String>,
+
+        /// 密文文件路径（文件内容为 hex 文本）
+        #[arg(short, long)]
+        file_path: Option<String>,
+    },
+
+    /// Replay a saved multi-cipher recipe (JSON) over the input (requires `--features serde`)
+    #[cfg(feature = "serde")]
+    Run {
+        /// 配方文件路径，格式如 {"stages":[{"algo":"caesar","key":"3"},{"algo":"rail_fence","key":"4"}]}
+        #[arg(long)]
+        recipe: String,
+
+        /// 要处理的文本
+        #[arg(short, long)]
+        text: Option<String>,
+
+        /// 待处理文本的文件路径
+        #[arg(short, long)]
+        file_path: Option<String>,
+
+        /// 按配方解密（各层倒序取逆），默认为加密
+        #[arg(short, long)]
+        decrypt: bool,
+    },
+
+    /// Print a histogram of letter frequencies (case-insensitive, non-letters ignored)
+    Freq {
+        /// 要统计的文本
+        #[arg(short, long)]
+        text: Option<String>,
+
+        /// 待统计文本的文件路径
         #[arg(short, long)]
         file_path: Option<String>,
     },
+
+    /// Print the 26x26 Vigenere square (tabula recta), a handy teaching aid
+    VigenereTable,
+}
+
+/// `--max-file-size` 的默认值：100 MB
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 100 * 1024 * 1024;
+
+/// 运行时行为选项：控制输入读取与结果展示，不影响加密结果本身（加密与解密共用）
+#[derive(Args, Debug, Clone)]
+pub struct RunOptions {
+    /// 读取大文件时在终端显示读取进度（仅当 stderr 为终端时生效）；
+    /// 加解密一次处理整段文本，这一步不显示进度
+    #[arg(long)]
+    pub progress: bool,
+
+    /// 操作结束后打印实际生效的（归一化后的）参数
+    #[arg(long)]
+    pub explain: bool,
+
+    /// 将结果复制到系统剪贴板（需以 `--features clipboard` 编译，否则仅打印警告）
+    #[arg(long)]
+    pub copy: bool,
+
+    /// 仅加密：输出前立即用同一配置解密一次，结果与原文不一致时中止并报错，
+    /// 用于发现有损的配置（如 Playfair 合并 I/J、Morse 丢失大小写）
+    #[arg(long)]
+    pub verify: bool,
+
+    /// 仅加密：去掉结果中的空白后每 N 个字符插入一个空格（传统的五字一组写法）；
+    /// 解密分组书写的密文请使用 `decrypt --grouped`
+    #[arg(long, value_name = "N")]
+    pub group: Option<NonZeroUsize>,
+
+    /// 仅加密：把结果包装成带标签的"装甲"文本块，头部记录算法名（不含密钥），便于分享；
+    /// 解密时会自动识别装甲文本块，并改用头部记录的算法
+    #[arg(long)]
+    pub armor: bool,
+
+    /// 输入文件的大小上限（字节），超过时拒绝读取，避免误把超大文件整个读入内存
+    #[arg(long, value_name = "BYTES", default_value_t = DEFAULT_MAX_FILE_SIZE)]
+    pub max_file_size: u64,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            progress: false,
+            explain: false,
+            copy: false,
+            verify: false,
+            group: None,
+            armor: false,
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+        }
+    }
+}
+
+/// 影响加密引擎构造方式的可选参数（加密与解密共用）
+///
+/// 通过 `#[command(flatten)]` 嵌入到各个子命令中，
+/// 交互模式下使用 `CipherOptions::default()`。
+#[derive(Args, Debug, Clone, Default)]
+pub struct CipherOptions {
+    /// 按名称使用算法注册表中的算法代替 `--algo`（如 `reverse_words`，以及嵌入方在运行时注册的自定义算法），
+    /// 密钥由注册的构造函数自行解析与校验；装甲文本块只记录 `--algo` 的算法名，因此不能与 `--armor` 同时使用
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["algo", "armor"])]
+    pub cipher: Option<String>,
+
+    /// 仅 XOR：使用 PKCS#7 将明文填充到块大小的整数倍以隐藏真实长度（默认块大小 16）
+    #[arg(
+        long,
+        value_name = "BLOCK_SIZE",
+        num_args = 0..=1,
+        default_missing_value = "16"
+    )]
+    pub timing_safe: Option<u8>,
+
+    /// 仅 XOR：加密时在明文前附加 CRC-32 校验和（一同加密），解密时校验，
+    /// 密钥错误时报 "integrity check failed"；加密与解密两端必须同时使用
+    #[arg(long)]
+    pub checksum: bool,
+
+    /// 仅替换类算法（Caesar / ROT13 / Atbash / Vigenere / Beaufort）：
+    /// 遇到非字母字符（包括空格、标点）时报错，而不是原样保留
+    #[arg(long)]
+    pub suppress_passthrough: bool,
+
+    /// 仅 Vigenere / Beaufort：非字母字符（空格、标点等）也推进密钥位置，
+    /// 默认只有字母才消耗密钥字母；加密与解密两端必须使用同一种策略
+    #[arg(long)]
+    pub count_non_letters: bool,
+
+    /// 仅 Caesar / Vigenere：文件名安全模式，数字也参与平移，`.`、`_`、`-` 原样保留，
+    /// 其他字符直接报错，保证合法文件名加密后仍是合法文件名
+    #[arg(long)]
+    pub safe_charset: bool,
+
+    /// 仅方阵类算法（Playfair / Polybius）：5×5 方阵中合并的字母对，可选 IJ（默认）、CK、UV
+    #[arg(long, value_name = "PAIR")]
+    pub merge: Option<Merge>,
+
+    /// 仅 Columnar：读出各列的顺序，keyword（默认）按关键词字母顺序，left-to-right 严格从左到右
+    #[arg(long, value_name = "ORDER")]
+    pub columns: Option<ReadOrder>,
+
+    /// 仅 Columnar：明文写入表格的方式，row-major（默认）逐行写入，column-major 逐列写入；
+    /// column-major 与 `--columns left-to-right` 组合不会改变文本，会被拒绝
+    #[arg(long, value_name = "ORDER")]
+    pub fill: Option<WriteOrder>,
+
+    /// 仅 XOR：密文的编码方式。hex / base64 关闭自动识别（同时决定加密输出的编码）；
+    /// raw 表示输入文件中直接存放异或后的原始字节，仅用于解密。不指定时自动识别 hex / base64
+    #[arg(long, value_enum, value_name = "ENCODING")]
+    pub input_encoding: Option<InputEncoding>,
+
+    /// 从标准输入读取密钥，代替 `-k`：stdin 为终端时以掩码方式输入（不回显），
+    /// 否则读取管道中的第一行，避免密钥出现在 shell 历史与进程列表中
+    #[arg(long, conflicts_with = "key")]
+    pub key_stdin: bool,
+
+    /// 仅 Caesar："每日密码"，偏移量由日期推导（一年中的第几天 mod 26），无需提供密钥
+    /// （需以 `--features daily` 编译）
+    #[cfg(feature = "daily")]
+    #[arg(long, conflicts_with_all = ["key", "key_stdin", "cipher"])]
+    pub daily: bool,
+
+    /// 与 `--daily` 搭配使用：用指定日期代替今天，便于复现或解密以前的消息
+    #[cfg(feature = "daily")]
+    #[arg(long, value_name = "YYYY-MM-DD", requires = "daily")]
+    pub date: Option<chrono::NaiveDate>,
+
+    /// 只对匹配该正则的区域加解密（有捕获组时只处理第 1 个捕获组），其余文本原样保留；
+    /// 解密时正则必须在密文上匹配到相同的区域（需以 `--features regex` 编译）
+    #[cfg(feature = "regex")]
+    #[arg(long, value_name = "REGEX")]
+    pub only_matching: Option<String>,
+
+    /// 加解密前先把文本与密钥规范化为 Unicode NFC 形式，使组合/分解两种写法的相同文本得到相同结果；
+    /// 注意这可能改变文本的字节长度，XOR 等按字节处理的算法的密文长度会随之变化
+    /// （需以 `--features normalize` 编译）
+    #[cfg(feature = "normalize")]
+    #[arg(long)]
+    pub normalize: bool,
+}
+
+/// XOR 密文的输入编码，对应 `--input-encoding`
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum, Debug)]
+pub enum InputEncoding {
+    /// 十六进制文本
+    Hex,
+    /// 标准 Base64 文本
+    Base64,
+    /// 原始字节（按字节读取文件，不做任何文本解码）
+    Raw,
 }
 
 // ============================================================================
@@ -134,7 +385,16 @@ pub enum Algorithm {
     Caesar,
     Rot13,
     RailFence,
+    Columnar,
     Base64,
     Vigenere,
+    Beaufort,
     Xor,
+    Morse,
+    Nato,
+    Atbash,
+    Playfair,
+    Polybius,
+    Reverse,
+    ReverseWords,
 }
@@ -0,0 +1,304 @@
+//! 列换位密码 (Columnar Transposition) 的实现
+//!
+//! 把明文写进一个宽度等于关键词长度的表格，再按列读出。字符本身不变，只改变位置。
+//! 表格最后一行可以不满（不做填充），因此密文长度与明文完全相同。
+//!
+//! 教材与各类工具中的列换位有不少变体，这里通过两个选项覆盖最常见的几种：
+//!
+//! * [`ReadOrder`]：读出各列的顺序——按关键词字母排序（默认，经典做法），或严格从左到右；
+//! * [`WriteOrder`]：写入表格的方式——逐行写入（默认），或逐列（从左到右）写入。
+//!
+//! 逐列写入后再从左到右逐列读出，得到的就是原文，因此这一组合会被拒绝。
+
+use crate::util::validate_ascii_alpha_key;
+use crate::{Cipher, CipherError, Inverse, KeyRequirement, Redacted};
+use std::fmt;
+use std::str::FromStr;
+
+/// 读出各列的顺序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReadOrder {
+    /// 按关键词字母的字母表顺序读出各列，相同字母按出现位置先后（默认）
+    #[default]
+    Keyword,
+    /// 严格从左到右读出各列，关键词只决定表格宽度
+    LeftToRight,
+}
+
+impl FromStr for ReadOrder {
+    type Err = CipherError;
+
+    /// 解析 `keyword` 或 `left-to-right`（不区分大小写）
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "keyword" => Ok(ReadOrder::Keyword),
+            "left-to-right" => Ok(ReadOrder::LeftToRight),
+            _ => Err(CipherError::InvalidInput(format!(
+                "unsupported column order '{}' (expected keyword or left-to-right)",
+                s
+            ))),
+        }
+    }
+}
+
+/// 明文写入表格的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WriteOrder {
+    /// 逐行写入（默认）
+    #[default]
+    RowMajor,
+    /// 从左到右逐列写入
+    ColumnMajor,
+}
+
+impl FromStr for WriteOrder {
+    type Err = CipherError;
+
+    /// 解析 `row-major` 或 `column-major`（不区分大小写）
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "row-major" => Ok(WriteOrder::RowMajor),
+            "column-major" => Ok(WriteOrder::ColumnMajor),
+            _ => Err(CipherError::InvalidInput(format!(
+                "unsupported fill order '{}' (expected row-major or column-major)",
+                s
+            ))),
+        }
+    }
+}
+
+/// 列换位密码
+#[derive(Clone)]
+pub struct Columnar {
+    /// 关键词长度，即表格的列数
+    width: usize,
+    /// 读出时依次访问的列号
+    columns: Vec<usize>,
+    read_order: ReadOrder,
+    write_order: WriteOrder,
+}
+
+impl Columnar {
+    /// 创建一个经典的列换位实例：逐行写入，按关键词字母顺序读出各列
+    ///
+    /// # 参数
+    ///
+    /// * `keyword` - 关键词，非空且仅含 ASCII 字母（不区分大小写），否则返回 `CipherError::InvalidKey`
+    pub fn new(keyword: &str) -> Result<Self, CipherError> {
+        Self::with_options(keyword, ReadOrder::Keyword, WriteOrder::RowMajor)
+    }
+
+    /// 指定读出顺序与写入方式创建实例
+    ///
+    /// `WriteOrder::ColumnMajor` 与 `ReadOrder::LeftToRight` 的组合不会改变文本，
+    /// 返回 `CipherError::InvalidKey`；其余组合都可以完整还原。
+    pub fn with_options(
+        keyword: &str,
+        read_order: ReadOrder,
+        write_order: WriteOrder,
+    ) -> Result<Self, CipherError> {
+        validate_ascii_alpha_key(keyword)?;
+        if read_order == ReadOrder::LeftToRight && write_order == WriteOrder::ColumnMajor {
+            return Err(CipherError::InvalidKey(
+                "column-major writing read left-to-right leaves the text unchanged".to_string(),
+            ));
+        }
+
+        let letters: Vec<u8> = keyword.bytes().map(|b| b.to_ascii_uppercase()).collect();
+        let mut columns: Vec<usize> = (0..letters.len()).collect();
+        if read_order == ReadOrder::Keyword {
+            // sort_by_key 是稳定排序，相同字母保持原有的先后顺序
+            columns.sort_by_key(|&column| letters[column]);
+        }
+
+        Ok(Self {
+            width: letters.len(),
+            columns,
+            read_order,
+            write_order,
+        })
+    }
+
+    /// 对 `len` 个字符的文本，加密是否不改变任何字符的位置
+    ///
+    /// 单字母关键词总是如此；关键词已按字母顺序排列时，只有一行的短文本、
+    /// 或者逐列写入的文本同样原样输出。
+    pub fn leaves_unchanged(&self, len: usize) -> bool {
+        self.permutation(len)
+            .into_iter()
+            .enumerate()
+            .all(|(position, index)| position == index)
+    }
+
+    /// 计算换位表：密文第 `j` 个字符取自明文第 `permutation[j]` 个字符
+    fn permutation(&self, len: usize) -> Vec<usize> {
+        let full_rows = len / self.width;
+        let remainder = len % self.width;
+        let height = |column: usize| full_rows + usize::from(column < remainder);
+
+        let mut permutation = Vec::with_capacity(len);
+        for &column in &self.columns {
+            // 逐列写入时，该列之前的所有列都已写满
+            let column_start: usize = (0..column).map(height).sum();
+            for row in 0..height(column) {
+                permutation.push(match self.write_order {
+                    WriteOrder::RowMajor => row * self.width + column,
+                    WriteOrder::ColumnMajor => column_start + row,
+                });
+            }
+        }
+        permutation
+    }
+}
+
+// 列的读出顺序会暴露关键词的字母排序，因此同样只输出关键词长度
+impl fmt::Debug for Columnar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Columnar")
+            .field(
+                "key",
+                &Redacted {
+                    len: Some(self.width),
+                },
+            )
+            .field("read_order", &self.read_order)
+            .field("write_order", &self.write_order)
+            .finish()
+    }
+}
+
+impl Cipher for Columnar {
+    fn encrypt(&self, text: &str) -> Result<String, CipherError> {
+        let chars: Vec<char> = text.chars().collect();
+        Ok(self
+            .permutation(chars.len())
+            .into_iter()
+            .map(|index| chars[index])
+            .collect())
+    }
+
+    fn decrypt(&self, text: &str) -> Result<String, CipherError> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut plain = vec![' '; chars.len()];
+        for (&index, &c) in self.permutation(chars.len()).iter().zip(&chars) {
+            plain[index] = c;
+        }
+        Ok(plain.into_iter().collect())
+    }
+
+    fn inverse(&self) -> Box<dyn Cipher> {
+        Box::new(Inverse(self.clone()))
+    }
+
+    fn key_requirement(&self) -> KeyRequirement {
+        KeyRequirement::Keyword
+    }
+
+    fn rekey(&self, new_key: &str) -> Result<Box<dyn Cipher>, CipherError> {
+        Ok(Box::new(Self::with_options(
+            new_key,
+            self.read_order,
+            self.write_order,
+        )?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columnar_textbook_example() {
+        // 关键词 ZEBRAS：列的读出顺序为 A(4) B(2) E(1) R(3) S(5) Z(0)
+        let cipher = Columnar::new("ZEBRAS").unwrap();
+        let encrypted = cipher.encrypt("WEAREDISCOVEREDFLEEATONCE").unwrap();
+        assert_eq!(encrypted, "EVLNACDTESEAROFODEECWIREE");
+        assert_eq!(
+            cipher.decrypt(&encrypted).unwrap(),
+            "WEAREDISCOVEREDFLEEATONCE"
+        );
+    }
+
+    #[test]
+    fn test_columnar_every_combination_roundtrips() {
+        let combinations = [
+            (ReadOrder::Keyword, WriteOrder::RowMajor),
+            (ReadOrder::Keyword, WriteOrder::ColumnMajor),
+            (ReadOrder::LeftToRight, WriteOrder::RowMajor),
+        ];
+        for (read_order, write_order) in combinations {
+            let cipher = Columnar::with_options("zebras", read_order, write_order).unwrap();
+            // 覆盖空串、短于关键词、恰好整行以及末行不满的情况，含空格与多字节字符
+            for text in ["", "hi", "abcdef", "Attack at dawn, 世界🦀!"] {
+                let encrypted = cipher.encrypt(text).unwrap();
+                assert_eq!(encrypted.chars().count(), text.chars().count());
+                assert_eq!(
+                    cipher.decrypt(&encrypted).unwrap(),
+                    text,
+                    "{:?} {:?}",
+                    read_order,
+                    write_order
+                );
+                assert_eq!(cipher.inverse().encrypt(&encrypted).unwrap(), text);
+            }
+        }
+    }
+
+    #[test]
+    fn test_columnar_read_orders_differ() {
+        let text = "WEAREDISCOVEREDFLEEATONCE";
+        let keyword = Columnar::new("ZEBRAS").unwrap();
+        let left_to_right =
+            Columnar::with_options("ZEBRAS", ReadOrder::LeftToRight, WriteOrder::RowMajor).unwrap();
+        assert_eq!(
+            left_to_right.encrypt(text).unwrap(),
+            "WIREEESEAACDTROFOEVLNDEEC"
+        );
+        assert_ne!(
+            keyword.encrypt(text).unwrap(),
+            left_to_right.encrypt(text).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_columnar_rejects_identity_combination_and_bad_keys() {
+        assert!(matches!(
+            Columnar::with_options("KEY", ReadOrder::LeftToRight, WriteOrder::ColumnMajor),
+            Err(CipherError::InvalidKey(_))
+        ));
+        assert!(matches!(Columnar::new(""), Err(CipherError::InvalidKey(_))));
+        assert!(matches!(
+            Columnar::new("KEY1"),
+            Err(CipherError::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_columnar_leaves_unchanged() {
+        assert!(Columnar::new("K").unwrap().leaves_unchanged(10));
+        // 已排序的关键词：单行文本原样输出，多行文本仍会被打乱
+        let sorted = Columnar::new("ABC").unwrap();
+        assert!(sorted.leaves_unchanged(3));
+        assert!(!sorted.leaves_unchanged(10));
+        let column_major =
+            Columnar::with_options("ABC", ReadOrder::Keyword, WriteOrder::ColumnMajor).unwrap();
+        assert!(column_major.leaves_unchanged(10));
+        assert!(!Columnar::new("ZEBRAS").unwrap().leaves_unchanged(25));
+    }
+
+    #[test]
+    fn test_order_from_str() {
+        assert_eq!("Left-To-Right".parse(), Ok(ReadOrder::LeftToRight));
+        assert_eq!("column-major".parse(), Ok(WriteOrder::ColumnMajor));
+        assert!("diagonal".parse::<ReadOrder>().is_err());
+    }
+
+    #[test]
+    fn test_columnar_debug_redacts_key() {
+        let cipher = Columnar::new("SECRET").unwrap();
+        assert_eq!(
+            format!("{:?}", cipher),
+            "Columnar { key: <redacted, len=6>, read_order: Keyword, write_order: RowMajor }"
+        );
+    }
+}
@@ -3,6 +3,7 @@
 //! 定义了 `ciphery` 库中可能出现的各种错误类型。
 
 use std::fmt;
+use std::io;
 
 /// 加密/解密过程中可能发生的错误
 ///
@@ -12,10 +13,16 @@ use std::fmt;
 pub enum CipherError {
     /// 输入数据无效（例如：包含了算法不支持的字符）
     InvalidInput(String),
+    /// 输入中某个符号无法识别（例如：摩尔斯电码中不存在的点划组合），
+    /// 携带该符号本身及其在输入中的起始字符位置（从 0 开始）
+    InvalidToken { token: String, position: usize },
     /// 密钥无效（例如：密钥长度不符合要求）
     InvalidKey(String),
     /// 十六进制编码错误
     HexCodingError(String),
+    /// 文件读写错误，携带出错的路径与 `io::ErrorKind`，
+    /// 方便调用方区分"文件不存在"与"权限不足"等情况
+    IoError { path: String, kind: io::ErrorKind },
     /// 其他内部错误
     Other(String),
 }
@@ -26,8 +33,16 @@ impl fmt::Display for CipherError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             CipherError::InvalidInput(msg) => write!(f, "无效的输入: {}", msg),
+            CipherError::InvalidToken { token, position } => write!(
+                f,
+                "无效的输入: invalid token '{}' at position {}",
+                token, position
+            ),
             CipherError::InvalidKey(msg) => write!(f, "无效的密钥: {}", msg),
             CipherError::HexCodingError(msg) => write!(f, "十六进制编码错误: {}", msg),
+            CipherError::IoError { path, kind } => {
+                write!(f, "文件读写错误: '{}' ({})", path, kind)
+            }
             CipherError::Other(msg) => write!(f, "加密引擎内部错误: {}", msg),
         }
     }
@@ -0,0 +1,271 @@
+//! 玩具 Feistel 分组密码（仅供教学，**不安全**）
+//!
+//! Feistel 网络是 DES 等经典分组密码的骨架：把每个分组拆成左右两半，每一轮执行
+//! `(L, R) -> (R, L ^ F(R, K_i))`。无论轮函数 `F` 是否可逆，整个网络都可逆——
+//! 解密只需按相反的顺序使用各轮子密钥，把同样的步骤倒着走一遍。
+//!
+//! 这里的轮函数与子密钥派生都极其简单，只用于演示 Feistel 结构本身，
+//! 切勿用来保护任何真实数据。
+//!
+//! 明文按 UTF-8 字节处理，使用 PKCS#7 填充到 [`BLOCK_SIZE`] 的整数倍，密文以 hex 编码输出。
+
+use crate::util::{pkcs7_pad, pkcs7_unpad};
+use crate::{Cipher, CipherError, Inverse, KeyRequirement, Redacted};
+use std::fmt;
+
+/// 分组大小（字节），左右两半各占一半
+pub const BLOCK_SIZE: usize = 8;
+
+/// 允许的最大轮数
+pub const MAX_ROUNDS: usize = 64;
+
+/// 按名称构造（[`crate::build_cipher`]）且未指定轮数时使用的轮数，与 DES 相同
+pub const DEFAULT_ROUNDS: usize = 16;
+
+/// 半个分组
+type Half = [u8; BLOCK_SIZE / 2];
+
+/// 教学用 Feistel 分组密码
+#[derive(Clone)]
+pub struct Feistel {
+    key: Vec<u8>,
+    /// 由密钥派生出的各轮子密钥，长度即轮数
+    round_keys: Vec<Half>,
+}
+
+impl Feistel {
+    /// 创建一个新的 Feistel 实例
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 任意非空字符串，按 UTF-8 字节使用，为空时返回 `CipherError::InvalidKey`
+    /// * `rounds` - 轮数，取值 1..=[`MAX_ROUNDS`]，否则返回 `CipherError::InvalidKey`
+    pub fn new(key: &str, rounds: usize) -> Result<Self, CipherError> {
+        if key.is_empty() {
+            return Err(CipherError::InvalidKey(
+                "Feistel key cannot be empty".to_string(),
+            ));
+        }
+        if !(1..=MAX_ROUNDS).contains(&rounds) {
+            return Err(CipherError::InvalidKey(format!(
+                "Feistel rounds must be between 1 and {}",
+                MAX_ROUNDS
+            )));
+        }
+
+        let key = key.as_bytes().to_vec();
+        let round_keys = (0..rounds)
+            .map(|round| derive_round_key(&key, round))
+            .collect();
+        Ok(Self { key, round_keys })
+    }
+
+    /// 轮数
+    pub fn rounds(&self) -> usize {
+        self.round_keys.len()
+    }
+
+    /// 加密一个分组：依次执行每一轮 `(L, R) -> (R, L ^ F(R, K_i))`
+    fn encrypt_block(&self, block: &mut [u8]) {
+        let (mut left, mut right) = split(block);
+        for round_key in &self.round_keys {
+            let next_right = xor_halves(left, round_function(right, round_key));
+            left = right;
+            right = next_right;
+        }
+        join(block, left, right);
+    }
+
+    /// 解密一个分组：倒序使用子密钥，执行每一轮的逆 `(L, R) -> (R ^ F(L, K_i), L)`
+    fn decrypt_block(&self, block: &mut [u8]) {
+        let (mut left, mut right) = split(block);
+        for round_key in self.round_keys.iter().rev() {
+            let previous_left = xor_halves(right, round_function(left, round_key));
+            right = left;
+            left = previous_left;
+        }
+        join(block, left, right);
+    }
+}
+
+/// 由密钥派生第 `round` 轮的子密钥：循环取密钥字节，并混入轮号，使各轮子密钥互不相同
+fn derive_round_key(key: &[u8], round: usize) -> Half {
+    let mut round_key = [0u8; BLOCK_SIZE / 2];
+    for (j, byte) in round_key.iter_mut().enumerate() {
+        let index = round * (BLOCK_SIZE / 2) + j;
+        *byte = key[index % key.len()] ^ (index as u8).wrapping_mul(0x9d).wrapping_add(0x3b);
+    }
+    round_key
+}
+
+/// 轮函数：与子密钥异或后做循环移位，并与相邻字节相加扩散（不需要可逆）
+fn round_function(half: Half, round_key: &Half) -> Half {
+    let mut output = [0u8; BLOCK_SIZE / 2];
+    for j in 0..output.len() {
+        let mixed = (half[j] ^ round_key[j]).rotate_left(3);
+        output[j] = mixed.wrapping_add(half[(j + 1) % half.len()]);
+    }
+    output
+}
+
+fn xor_halves(a: Half, b: Half) -> Half {
+    std::array::from_fn(|j| a[j] ^ b[j])
+}
+
+fn split(block: &[u8]) -> (Half, Half) {
+    let (left, right) = block.split_at(BLOCK_SIZE / 2);
+    (
+        left.try_into().expect("block has BLOCK_SIZE bytes"),
+        right.try_into().expect("block has BLOCK_SIZE bytes"),
+    )
+}
+
+fn join(block: &mut [u8], left: Half, right: Half) {
+    block[..BLOCK_SIZE / 2].copy_from_slice(&left);
+    block[BLOCK_SIZE / 2..].copy_from_slice(&right);
+}
+
+impl fmt::Debug for Feistel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Feistel")
+            .field(
+                "key",
+                &Redacted {
+                    len: Some(self.key.len()),
+                },
+            )
+            .field("rounds", &self.rounds())
+            .finish()
+    }
+}
+
+impl Cipher for Feistel {
+    fn encrypt(&self, text: &str) -> Result<String, CipherError> {
+        let mut bytes = pkcs7_pad(text.as_bytes().to_vec(), BLOCK_SIZE as u8);
+        for block in bytes.chunks_mut(BLOCK_SIZE) {
+            self.encrypt_block(block);
+        }
+        Ok(hex::encode(bytes))
+    }
+
+    fn decrypt(&self, text: &str) -> Result<String, CipherError> {
+        let mut bytes = hex::decode(text.trim()).map_err(|e| {
+            CipherError::HexCodingError(format!("Feistel decryption failed: {}", e))
+        })?;
+        if !bytes.len().is_multiple_of(BLOCK_SIZE) {
+            return Err(CipherError::InvalidInput(format!(
+                "Feistel ciphertext must be a multiple of {} bytes",
+                BLOCK_SIZE
+            )));
+        }
+        for block in bytes.chunks_mut(BLOCK_SIZE) {
+            self.decrypt_block(block);
+        }
+
+        let bytes = pkcs7_unpad(bytes, BLOCK_SIZE as u8).ok_or_else(|| {
+            CipherError::InvalidInput("Feistel decryption failed: invalid padding".to_string())
+        })?;
+        String::from_utf8(bytes)
+            .map_err(|e| CipherError::InvalidInput(format!("Feistel decryption failed: {}", e)))
+    }
+
+    fn inverse(&self) -> Box<dyn Cipher> {
+        // 输出是 hex 编码，不能直接对密文再"加密"，需要包装
+        Box::new(Inverse(self.clone()))
+    }
+
+    fn key_requirement(&self) -> KeyRequirement {
+        KeyRequirement::Bytes
+    }
+
+    fn rekey(&self, new_key: &str) -> Result<Box<dyn Cipher>, CipherError> {
+        // 轮数沿用
+        Ok(Box::new(Self::new(new_key, self.rounds())?))
+    }
+
+    fn output_is_binary_safe(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feistel_multi_round_roundtrip() {
+        for rounds in [1, 2, 8, 16, MAX_ROUNDS] {
+            let cipher = Feistel::new("secret", rounds).unwrap();
+            for text in [
+                "",
+                "hi",
+                "exactly8",
+                "Hello, 世界 🦀! A longer message over blocks.",
+            ] {
+                let encrypted = cipher.encrypt(text).unwrap();
+                // 填充后总是整块，且已对齐时也会补一整块
+                assert_eq!(
+                    encrypted.len(),
+                    (text.len() / BLOCK_SIZE + 1) * BLOCK_SIZE * 2
+                );
+                assert_eq!(
+                    cipher.decrypt(&encrypted).unwrap(),
+                    text,
+                    "{} rounds",
+                    rounds
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_feistel_rounds_and_key_change_ciphertext() {
+        let text = "Attack at dawn";
+        let four = Feistel::new("secret", 4).unwrap().encrypt(text).unwrap();
+        let five = Feistel::new("secret", 5).unwrap().encrypt(text).unwrap();
+        let other_key = Feistel::new("Secret", 4).unwrap().encrypt(text).unwrap();
+        assert_ne!(four, five);
+        assert_ne!(four, other_key);
+
+        // 轮数不同的实例无法解密彼此的密文
+        assert_ne!(
+            Feistel::new("secret", 5).unwrap().decrypt(&four).ok(),
+            Some(text.to_string())
+        );
+    }
+
+    #[test]
+    fn test_feistel_rejects_bad_parameters_and_input() {
+        assert!(matches!(
+            Feistel::new("", 4),
+            Err(CipherError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            Feistel::new("key", 0),
+            Err(CipherError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            Feistel::new("key", MAX_ROUNDS + 1),
+            Err(CipherError::InvalidKey(_))
+        ));
+
+        let cipher = Feistel::new("key", 4).unwrap();
+        assert!(matches!(
+            cipher.decrypt("zz"),
+            Err(CipherError::HexCodingError(_))
+        ));
+        assert!(matches!(
+            cipher.decrypt("0011"),
+            Err(CipherError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_feistel_debug_redacts_key() {
+        let cipher = Feistel::new("secret", 8).unwrap();
+        assert_eq!(
+            format!("{:?}", cipher),
+            "Feistel { key: <redacted, len=6>, rounds: 8 }"
+        );
+    }
+}
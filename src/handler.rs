@@ -3,36 +3,90 @@
 //! 本模块负责接收解析后的命令行参数，并调用对应的加密/解密引擎执行操作。
 //! 将"做什么事"的逻辑与 CLI 参数定义和程序入口分离开来。
 
-use ciphery::{Cipher, caesar, rail_fence, vigenere, xor};
-use dialoguer::{Input, Select, theme::ColorfulTheme};
-use std::fs;
+use ciphery::{
+    Cipher, CipherError, analysis, armor, atbash, beaufort, caesar, columnar, morse, nato,
+    playfair, polybius, rail_fence, reverse, vigenere, xor,
+};
+use clap::ValueEnum;
+use dialoguer::{Input, Password, Select, theme::ColorfulTheme};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, IsTerminal, Write};
 // ciphery代表外部的库Crate，使用具体的包名（如 ciphery、clap、std）代表引入一个外部的 Crate。
-use crate::cli::{Algorithm, Commands, print_banner};
+use crate::cli::{Algorithm, CipherOptions, Commands, InputEncoding, RunOptions, print_banner};
+use crate::logging::{error, info, warning};
+use crate::progress::{self, Progress};
 // carte:: 代表当前 crate 的根模块，因为 handler.rs 是被 main.rs 声明和引入的模块，所以它属于你的二进制 Crate (Binary Crate)。在这里，crate:: 就等同于从 main.rs 开始查找。
 
 // ====== 公共入口：根据子命令分发执行 ======
 /// 根据解析到的子命令分发到对应的处理逻辑
+///
+/// 各处理函数通过 `?` 向上传递错误，这里统一打印一次。
 pub fn run(command: Option<&Commands>) {
-    match command {
+    let result = match command {
         Some(Commands::Encrypt {
             text,
             algo,
             key,
             file_path,
-        }) => {
-            handle_encrypt(text, algo, key, file_path);
-        }
+            run,
+            options,
+        }) => handle_encrypt(text, algo, key, file_path, run, options),
         Some(Commands::Decrypt {
             text,
             algo,
             key,
             file_path,
+            run,
+            options,
+            brute,
+            top,
+            show_scores,
+            lines,
+            continue_on_error,
+            grouped,
         }) => {
-            handle_decrypt(text, algo, key, file_path);
+            if run.group.is_some() {
+                // 解密时组宽没有意义，与其静默忽略，不如指向真正起作用的 `--grouped`
+                Err(CipherError::InvalidInput(
+                    "--group only applies to encryption; use --grouped to decrypt grouped ciphertext"
+                        .to_string(),
+                ))
+            } else if *lines {
+                handle_decrypt_lines(text, algo, key, file_path, run, options, *continue_on_error)
+            } else if *brute {
+                // --show-scores 要求列出全部候选，与 --top 互斥（由 clap 保证）
+                let top = if *show_scores { None } else { *top };
+                handle_brute_force(text, algo, file_path, run, top)
+            } else {
+                handle_decrypt(text, algo, key, file_path, run, options, *grouped)
+            }
+        }
+        Some(Commands::Crack {
+            text,
+            file_path,
+            show_scores,
+        }) => handle_crack(text, file_path, *show_scores),
+        Some(Commands::CrackXor { text, file_path }) => handle_crack_xor(text, file_path),
+        #[cfg(feature = "serde")]
+        Some(Commands::Run {
+            recipe,
+            text,
+            file_path,
+            decrypt,
+        }) => handle_run(recipe, text, file_path, *decrypt),
+        Some(Commands::Freq { text, file_path }) => handle_freq(text, file_path),
+        Some(Commands::VigenereTable) => {
+            println!("{}", vigenere::tabula_recta());
+            Ok(())
         }
         None => {
             handle_interactive();
+            Ok(())
         }
+    };
+    if let Err(e) = result {
+        error!("{}", e);
     }
     print_exit_message();
 }
@@ -43,53 +97,426 @@ fn handle_encrypt(
     text: &Option<String>,
     algo: &Algorithm,
     key: &Option<String>,
-    file_path: &Option<String>,
-) {
-    println!("[info] Encryption mode...");
-    println!("[info] Algorithm: {:?}", algo);
+    file_paths: &[String],
+    run: &RunOptions,
+    options: &CipherOptions,
+) -> Result<(), CipherError> {
+    info!("Encryption mode...");
+    info!("Algorithm: {}", algorithm_label(*algo, options));
+    let key = &resolve_key(*algo, key, options)?;
 
-    // 获取待加密文本：优先使用 --text，其次从 --file-path 读取
-    let plaintext = match resolve_input_text(text, file_path) {
-        Some(t) => t,
-        None => return,
-    };
-
-    let algorithm = *algo;
+    if options.input_encoding == Some(InputEncoding::Raw) {
+        return Err(CipherError::InvalidInput(
+            "--input-encoding raw is only supported for decryption".to_string(),
+        ));
+    }
 
-    // 校验密钥
-    if !validate_key(key, algorithm) {
-        return;
+    // 未直接提供文本且传入了多个文件时，进入批量处理
+    if text.is_none() && file_paths.len() > 1 {
+        validate_key(key, *algo, options, None)?;
+        return handle_batch(*algo, key, options, file_paths, run, true, false);
     }
 
-    // 执行加密
-    execute_encrypt(algorithm, &plaintext, key);
+    // 获取待加密文本：优先使用 --text，其次从 --file-path 读取
+    let plaintext = resolve_input_text(text, file_paths, run)?;
+
+    // 校验密钥并执行加密
+    validate_key(key, *algo, options, Some(&plaintext))?;
+    report_key_coverage(*algo, key, &plaintext);
+    let outcome = execute_encrypt(*algo, &plaintext, key, options, run)?;
+    report_outcome(&outcome, run);
+    Ok(())
 }
 
 /// 处理解密命令
+///
+/// `grouped` 为 `true`（`--grouped`）时，视为输入已按组书写，解密前去掉所有空白。
 fn handle_decrypt(
     text: &Option<String>,
     algo: &Algorithm,
     key: &Option<String>,
-    file_path: &Option<String>,
-) {
-    println!("[info] Decryption mode...");
-    println!("[info] Algorithm: {:?}", algo);
+    file_paths: &[String],
+    run: &RunOptions,
+    options: &CipherOptions,
+    grouped: bool,
+) -> Result<(), CipherError> {
+    info!("Decryption mode...");
+    info!("Algorithm: {}", algorithm_label(*algo, options));
+    let key = &resolve_key(*algo, key, options)?;
+
+    // 原始字节无法作为 UTF-8 文本读取，需要单独的字节输入路径
+    if options.input_encoding == Some(InputEncoding::Raw) {
+        if grouped {
+            return Err(CipherError::InvalidInput(
+                "--grouped cannot be combined with --input-encoding raw".to_string(),
+            ));
+        }
+        validate_key(key, *algo, options, None)?;
+        let outcome = execute_decrypt_raw(*algo, text, file_paths, key, run, options)?;
+        report_key_coverage(*algo, key, &outcome.output);
+        report_outcome(&outcome, run);
+        return Ok(());
+    }
+
+    // 未直接提供文本且传入了多个文件时，进入批量处理
+    if text.is_none() && file_paths.len() > 1 {
+        validate_key(key, *algo, options, None)?;
+        return handle_batch(*algo, key, options, file_paths, run, false, grouped);
+    }
 
     // 获取待解密文本
-    let ciphertext = match resolve_input_text(text, file_path) {
-        Some(t) => t,
-        None => return,
+    let ciphertext = resolve_input_text(text, file_paths, run)?;
+
+    // 装甲文本块：取出正文，并改用头部记录的算法（头部只记录 `--algo` 的算法名，`--cipher` 时不识别）
+    let (algo, ciphertext) = if options.cipher.is_some() {
+        (*algo, ciphertext)
+    } else {
+        unarmor(*algo, ciphertext)?
+    };
+
+    // 分组书写的密文：先去掉分组用的空白
+    let ciphertext = if grouped {
+        strip_grouping(&ciphertext)
+    } else {
+        ciphertext
     };
 
-    let algorithm = *algo;
+    // 校验密钥并执行解密
+    validate_key(key, algo, options, Some(&ciphertext))?;
+    let outcome = execute_decrypt(algo, &ciphertext, key, options)?;
+    // 密文可能经过 hex / base64 编码，长度比例以解密得到的明文为准
+    report_key_coverage(algo, key, &outcome.output);
+    report_outcome(&outcome, run);
+    Ok(())
+}
+
+/// 处理逐行解密（`--lines`）
+///
+/// 每一行单独解密并输出；`continue_on_error` 为 `true` 时跳过失败的行，最后打印汇总。
+fn handle_decrypt_lines(
+    text: &Option<String>,
+    algo: &Algorithm,
+    key: &Option<String>,
+    file_paths: &[String],
+    run: &RunOptions,
+    options: &CipherOptions,
+    continue_on_error: bool,
+) -> Result<(), CipherError> {
+    info!("Line-by-line decryption mode...");
+    info!("Algorithm: {}", algorithm_label(*algo, options));
+    let key = &resolve_key(*algo, key, options)?;
 
-    // 校验密钥
-    if !validate_key(key, algorithm) {
-        return;
+    let ciphertext = resolve_input_text(text, file_paths, run)?;
+    validate_key(key, *algo, options, None)?;
+    let cipher = build_cipher(*algo, key, options)?;
+
+    println!("[result] Decrypted lines:");
+    let failed = decrypt_lines(
+        cipher.as_ref(),
+        &ciphertext,
+        continue_on_error,
+        &mut io::stdout(),
+        &mut io::stderr(),
+    )?;
+    if continue_on_error {
+        println!(
+            "[summary] {} lines decrypted, {} failed",
+            ciphertext.lines().count() - failed,
+            failed
+        );
     }
+    Ok(())
+}
+
+/// 逐行解密 `text`，结果逐行写入 `out`，失败的行号（从 1 开始）与错误写入 `err`
+///
+/// `continue_on_error` 为 `false` 时遇到第一个失败的行即返回该错误；
+/// 为 `true` 时原样输出失败的行并继续处理，返回失败的行数。
+fn decrypt_lines(
+    cipher: &dyn Cipher,
+    text: &str,
+    continue_on_error: bool,
+    out: &mut impl Write,
+    err: &mut impl Write,
+) -> Result<usize, CipherError> {
+    ensure_decryptable(cipher)?;
+    let write_error = |e: io::Error| CipherError::Other(format!("failed to write output: {}", e));
+
+    let mut failed = 0;
+    for (index, line) in text.lines().enumerate() {
+        match cipher.decrypt(line) {
+            Ok(plain) => writeln!(out, "{}", plain).map_err(write_error)?,
+            Err(e) => {
+                writeln!(err, "[failed] line {}: {}", index + 1, e).map_err(write_error)?;
+                if !continue_on_error {
+                    return Err(e);
+                }
+                failed += 1;
+                writeln!(out, "{}", line).map_err(write_error)?;
+            }
+        }
+    }
+    Ok(failed)
+}
+
+/// 批量处理多个文件
+///
+/// 每个文件单独加密/解密，结果写入同目录下的 `<原文件名>.enc`（加密）或 `<原文件名>.dec`（解密）。
+/// 解密时 `grouped` 的含义同 [`handle_decrypt`]；单个文件失败不会中断整个批次，最后统一打印成功/失败汇总；
+/// 只有无法构造加密引擎（如密钥非法）时才返回错误。
+fn handle_batch(
+    algorithm: Algorithm,
+    key: &Option<String>,
+    options: &CipherOptions,
+    file_paths: &[String],
+    run: &RunOptions,
+    is_encrypt: bool,
+    grouped: bool,
+) -> Result<(), CipherError> {
+    let cipher = build_cipher(algorithm, key, options)?;
+    if !is_encrypt {
+        ensure_decryptable(cipher.as_ref())?;
+    }
+
+    let mut failed = 0;
+    for path in file_paths {
+        match process_file(cipher.as_ref(), algorithm, path, run, is_encrypt, grouped) {
+            Ok(output_path) => println!("[ok] {} -> {}", path, output_path),
+            Err(e) => {
+                println!("[failed] {}: {}", path, e);
+                failed += 1;
+            }
+        }
+    }
+
+    println!(
+        "[summary] {} succeeded, {} failed",
+        file_paths.len() - failed,
+        failed
+    );
+    Ok(())
+}
+
+/// 处理单个文件，成功时返回输出文件路径
+fn process_file(
+    cipher: &dyn Cipher,
+    algorithm: Algorithm,
+    path: &str,
+    run: &RunOptions,
+    is_encrypt: bool,
+    grouped: bool,
+) -> Result<String, CipherError> {
+    let content = read_input_file(path, run)?;
 
-    // 执行解密
-    execute_decrypt(algorithm, &ciphertext, key);
+    let (result, extension) = if is_encrypt {
+        let mut encrypted = cipher.encrypt(&content)?;
+        if run.verify {
+            verify_roundtrip(cipher, &content, &encrypted)?;
+        }
+        if let Some(size) = run.group {
+            encrypted = group_text(&encrypted, size.get());
+        }
+        if run.armor {
+            encrypted = armor::armor(&algorithm_name(algorithm), &encrypted);
+        }
+        (encrypted, "enc")
+    } else {
+        // 批量解密共用同一个引擎，装甲头部记录的算法必须与之一致
+        let (armored_algorithm, content) = unarmor(algorithm, content)?;
+        if armored_algorithm != algorithm {
+            return Err(CipherError::InvalidInput(format!(
+                "armored message was encrypted with {:?}, but {:?} was selected",
+                armored_algorithm, algorithm
+            )));
+        }
+        if grouped {
+            (cipher.decrypt(&strip_grouping(&content))?, "dec")
+        } else {
+            (cipher.decrypt(&content)?, "dec")
+        }
+    };
+
+    let output_path = format!("{}.{}", path, extension);
+    fs::write(&output_path, result).map_err(|e| CipherError::IoError {
+        path: output_path.clone(),
+        kind: e.kind(),
+    })?;
+    Ok(output_path)
+}
+
+/// 处理暴力破解（`decrypt --brute`）
+///
+/// 尝试所有偏移量，并按英文字母频率打分，从最像英文的结果开始列出。
+fn handle_brute_force(
+    text: &Option<String>,
+    algo: &Algorithm,
+    file_paths: &[String],
+    run: &RunOptions,
+    top: Option<usize>,
+) -> Result<(), CipherError> {
+    info!("Brute-force mode...");
+    info!("Algorithm: {:?}", algo);
+
+    if *algo != Algorithm::Caesar {
+        return Err(CipherError::InvalidInput(
+            "Brute force is only supported for Caesar cipher!".to_string(),
+        ));
+    }
+
+    let ciphertext = resolve_input_text(text, file_paths, run)?;
+
+    // 未指定 --top 时列出全部 26 个候选
+    let candidates = caesar::top_candidates(&ciphertext, top.unwrap_or(26));
+    println!("[result] Candidates (best first):");
+    let lines = format_scored(candidates.iter().map(|(shift, candidate, score)| {
+        (format!("shift {:>2}", shift), *score, candidate.as_str())
+    }));
+    for line in lines {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// 处理自动破解（`crack`）
+///
+/// 依次尝试 Caesar 全部偏移量、ROT13 与 Atbash，打印最像英文的那一个候选；
+/// `show_scores` 为 `true` 时额外列出所有候选及其得分。
+fn handle_crack(
+    text: &Option<String>,
+    file_path: &Option<String>,
+    show_scores: bool,
+) -> Result<(), CipherError> {
+    info!("Crack mode...");
+
+    let file_paths: Vec<String> = file_path.iter().cloned().collect();
+    let ciphertext = resolve_input_text(text, &file_paths, &RunOptions::default())?;
+
+    let candidates = analysis::crack(&ciphertext);
+    let best = candidates
+        .first()
+        .ok_or_else(|| CipherError::Other("No candidate found!".to_string()))?;
+    println!(
+        "[result] Best guess: {} (score {:.2})",
+        best.method, best.score
+    );
+    println!("{}", best.plaintext);
+
+    if show_scores {
+        println!("[result] All candidates (best first):");
+        let lines = format_scored(
+            candidates
+                .iter()
+                .map(|c| (c.method.to_string(), c.score, c.plaintext.as_str())),
+        );
+        for line in lines {
+            println!("{}", line);
+        }
+    }
+    Ok(())
+}
+
+/// 处理单字节 XOR 破解（`crack-xor`）
+///
+/// 尝试全部 256 个单字节密钥，打印最像英文的密钥与明文。
+fn handle_crack_xor(text: &Option<String>, file_path: &Option<String>) -> Result<(), CipherError> {
+    info!("XOR crack mode...");
+
+    let file_paths: Vec<String> = file_path.iter().cloned().collect();
+    let ciphertext = resolve_input_text(text, &file_paths, &RunOptions::default())?;
+
+    let (key, plaintext) = analysis::crack_single_byte_xor(&ciphertext).ok_or_else(|| {
+        CipherError::Other(
+            "No single-byte key yields readable text (is the input hex-encoded?)".to_string(),
+        )
+    })?;
+    println!("[result] Best key: {}", format_xor_key(key));
+    println!("{}", plaintext);
+    Ok(())
+}
+
+/// 以 `0x58 ('X')` 的形式展示单字节密钥，不可打印的字节只显示十六进制
+fn format_xor_key(key: u8) -> String {
+    if key.is_ascii_graphic() {
+        format!("0x{:02x} ('{}')", key, key as char)
+    } else {
+        format!("0x{:02x}", key)
+    }
+}
+
+/// 处理配方回放（`run --recipe`）：按 JSON 配方构造组合算法，对输入加密或解密
+#[cfg(feature = "serde")]
+fn handle_run(
+    recipe: &str,
+    text: &Option<String>,
+    file_path: &Option<String>,
+    decrypt: bool,
+) -> Result<(), CipherError> {
+    info!("Recipe mode...");
+
+    let pipeline =
+        ciphery::pipeline::Pipeline::from_json(&read_input_file(recipe, &RunOptions::default())?)?;
+    info!("Recipe loaded: {} stage(s)", pipeline.len());
+
+    let file_paths: Vec<String> = file_path.iter().cloned().collect();
+    let input = resolve_input_text(text, &file_paths, &RunOptions::default())?;
+    if decrypt {
+        println!("[result] Decrypted text:\n{}", pipeline.decrypt(&input)?);
+    } else {
+        println!("[result] Encrypted text:\n{}", pipeline.encrypt(&input)?);
+    }
+    Ok(())
+}
+
+/// 处理字母频率统计（`freq`）
+fn handle_freq(text: &Option<String>, file_path: &Option<String>) -> Result<(), CipherError> {
+    info!("Frequency analysis mode...");
+
+    let file_paths: Vec<String> = file_path.iter().cloned().collect();
+    let text = resolve_input_text(text, &file_paths, &RunOptions::default())?;
+
+    let counts = analysis::letter_frequency(&text);
+    println!(
+        "[result] Letter frequencies ({} letters):",
+        counts.iter().sum::<u32>()
+    );
+    for line in format_histogram(&counts) {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// 直方图中最长的柱子所占的字符数
+const HISTOGRAM_WIDTH: u32 = 40;
+
+/// 将字母计数格式化为 26 行直方图：`a |####     3`，柱长按最大计数缩放到 [`HISTOGRAM_WIDTH`]
+fn format_histogram(counts: &[u32; 26]) -> Vec<String> {
+    let max = counts.iter().copied().max().unwrap_or(0).max(1);
+    counts
+        .iter()
+        .zip('a'..='z')
+        .map(|(&count, letter)| {
+            // 在 u64 中计算，避免大文件的计数乘以柱宽后溢出 u32
+            let len = (u64::from(count) * u64::from(HISTOGRAM_WIDTH)).div_ceil(u64::from(max));
+            let bar = "#".repeat(len as usize);
+            format!(
+                "{} |{:<width$} {}",
+                letter,
+                bar,
+                count,
+                width = HISTOGRAM_WIDTH as usize
+            )
+        })
+        .collect()
+}
+
+/// 将 `(标签, 得分, 候选明文)` 格式化为逐行输出，顺序保持不变（调用方负责按得分排序）
+///
+/// 得分为卡方值，越小越像英文。
+fn format_scored<'a>(candidates: impl IntoIterator<Item = (String, f64, &'a str)>) -> Vec<String> {
+    candidates
+        .into_iter()
+        .map(|(label, score, plaintext)| format!("{} (score {:>8.2}): {}", label, score, plaintext))
+        .collect()
 }
 
 // ============================================================================
@@ -117,7 +544,7 @@ fn handle_interactive() {
         {
             Ok(idx) => idx,
             Err(_) => {
-                println!("[error] Failed to read your selection. Exiting.");
+                error!("Failed to read your selection. Exiting.");
                 break;
             }
         };
@@ -133,9 +560,18 @@ fn handle_interactive() {
         let algorithms = &[
             "Caesar",
             "ROT13",
+            "Atbash",
             "Vigenere",
+            "Beaufort",
             "Xor",
             "Rail Fence",
+            "Columnar",
+            "Morse",
+            "NATO Phonetic",
+            "Playfair",
+            "Polybius",
+            "Reverse",
+            "Reverse Words",
             "Base64 (coming soon)",
         ];
         let algo_index = match Select::with_theme(&theme)
@@ -146,7 +582,7 @@ fn handle_interactive() {
         {
             Ok(idx) => idx,
             Err(_) => {
-                println!("[error] Failed to read your selection.");
+                error!("Failed to read your selection.");
                 continue;
             }
         };
@@ -154,19 +590,26 @@ fn handle_interactive() {
         let algorithm = match algo_index {
             0 => Algorithm::Caesar,
             1 => Algorithm::Rot13,
-            2 => Algorithm::Vigenere,
-            3 => Algorithm::Xor,
-            4 => Algorithm::RailFence,
+            2 => Algorithm::Atbash,
+            3 => Algorithm::Vigenere,
+            4 => Algorithm::Beaufort,
+            5 => Algorithm::Xor,
+            6 => Algorithm::RailFence,
+            7 => Algorithm::Columnar,
+            8 => Algorithm::Morse,
+            9 => Algorithm::Nato,
+            10 => Algorithm::Playfair,
+            11 => Algorithm::Polybius,
+            12 => Algorithm::Reverse,
+            13 => Algorithm::ReverseWords,
             _ => {
-                println!(
-                    "[warning] This algorithm is not implemented yet. Please choose another.\n"
-                );
+                warning!("This algorithm is not implemented yet. Please choose another.\n");
                 continue;
             }
         };
 
         // ====== Step 3: 选择文本来源：直接输入 or 文件传入 ======
-        let text_source = &["Terminal", "File"];
+        let text_source = &["Terminal", "Paste (multi-line)", "File"];
         let text_source_index = match Select::with_theme(&theme)
             .with_prompt("Choose an algorithm")
             .items(text_source)
@@ -175,7 +618,7 @@ fn handle_interactive() {
         {
             Ok(idx) => idx,
             Err(_) => {
-                println!("[error] Failed to read your selection.");
+                error!("Failed to read your selection.");
                 continue;
             }
         };
@@ -191,11 +634,26 @@ fn handle_interactive() {
             {
                 Ok(t) => t,
                 Err(_) => {
-                    println!("[error] Failed to read your input.");
+                    error!("Failed to read your input.");
                     continue;
                 }
             },
 
+            // 粘贴模式：逐行读取，直到单独一行 "." 或 EOF
+            1 => {
+                println!(
+                    "Paste your text below. End with a line containing only '{}' (or EOF).",
+                    PASTE_SENTINEL
+                );
+                match read_multiline(io::stdin().lock()) {
+                    Ok(t) => t,
+                    Err(_) => {
+                        error!("Failed to read your input.");
+                        continue;
+                    }
+                }
+            }
+
             // 如果是文件，则从文件中读取文本
             _ => match Input::<String>::with_theme(&theme)
                 .with_prompt(if is_encrypt {
@@ -209,16 +667,16 @@ fn handle_interactive() {
                     // 去除用户可能误加的引号和空白
                     let cleaned_path = fp.trim().trim_matches('"').trim_matches('\'');
                     // 文件是否读取成功也需要模式匹配
-                    match fs::read_to_string(cleaned_path) {
+                    match read_input_file(cleaned_path, &RunOptions::default()) {
                         Ok(content) => content,
                         Err(e) => {
-                            println!("[error] Failed to read file '{}': {}", cleaned_path, e);
+                            error!("{}", e);
                             continue;
                         }
                     }
                 }
                 Err(_) => {
-                    println!("[error] Failed to read your input.");
+                    error!("Failed to read your input.");
                     continue;
                 }
             },
@@ -226,14 +684,21 @@ fn handle_interactive() {
 
         // ====== Step 4: 输入密钥（如果算法需要） ======
         let key: Option<String> = match algorithm {
-            Algorithm::Caesar | Algorithm::Vigenere | Algorithm::Xor | Algorithm::RailFence => {
-                let k: String = match Input::with_theme(&theme)
+            Algorithm::Caesar
+            | Algorithm::Vigenere
+            | Algorithm::Beaufort
+            | Algorithm::Playfair
+            | Algorithm::Xor
+            | Algorithm::RailFence
+            | Algorithm::Columnar => {
+                // 掩码输入，密钥不会回显在终端上
+                let k: String = match Password::with_theme(&theme)
                     .with_prompt("Enter the key (e.g. shift amount, or keyword)")
-                    .interact_text()
+                    .interact()
                 {
                     Ok(k) => k,
                     Err(_) => {
-                        println!("[error] Failed to read your input.");
+                        error!("Failed to read your input.");
                         continue;
                     }
                 };
@@ -245,10 +710,19 @@ fn handle_interactive() {
 
         // ====== Step 5: 执行加密/解密 ======
         println!(); // 空行，让输出更美观
-        if is_encrypt {
-            execute_encrypt(algorithm, &text, &key);
+        let result = if is_encrypt {
+            execute_encrypt(
+                algorithm,
+                &text,
+                &key,
+                &CipherOptions::default(),
+                &RunOptions::default(),
+            )
         } else {
-            execute_decrypt(algorithm, &text, &key);
+            execute_decrypt(algorithm, &text, &key, &CipherOptions::default())
+        };
+        if let Err(e) = result {
+            error!("{}", e);
         }
         println!(); // 空行分隔，准备下一轮循环
     }
@@ -258,176 +732,1513 @@ fn handle_interactive() {
 // 核心执行函数（供 CLI 模式和交互模式共用）
 // ============================================================================
 
-/// 执行加密操作
-fn execute_encrypt(algorithm: Algorithm, text: &str, key: &Option<String>) {
-    match algorithm {
-        Algorithm::Caesar => {
-            let shift = parse_caesar_key(key);
-            let cipher = caesar::Caesar::new(shift);
-            match cipher.encrypt(text) {
-                Ok(encrypted) => println!("[result] Encrypted text:\n{}", encrypted),
-                Err(e) => println!("[error] Encryption failed:\n{}", e),
-            }
-        }
-        Algorithm::Rot13 => {
-            let shift = 13;
-            let cipher = caesar::Caesar::new(shift);
-            match cipher.encrypt(text) {
-                Ok(encrypted) => println!("[result] Encrypted text:\n{}", encrypted),
-                Err(e) => println!("[error] Encryption failed:\n{}", e),
-            }
-        }
-        Algorithm::Vigenere => {
-            let key = key.as_ref().unwrap();
-            let cipher = vigenere::Vigenere::new(key);
-            match cipher.encrypt(text) {
-                Ok(encrypted) => println!("[result] Encrypted text:\n{}", encrypted),
-                Err(e) => println!("[error] Encryption failed:\n{}", e),
-            }
-        }
-        Algorithm::Xor => {
-            let key = key.as_ref().unwrap();
-            let cipher = xor::Xor::new(key);
-            match cipher.encrypt(text) {
-                Ok(encrypted) => println!("[result] Encrypted text:\n{}", encrypted),
-                Err(e) => println!("[error] Encryption failed:\n{}", e),
-            }
-        }
-        Algorithm::RailFence => {
-            let rails = parse_rail_fence_key(key);
-            match rail_fence::RailFence::new(rails) {
-                Ok(cipher) => match cipher.encrypt(text) {
-                    Ok(encrypted) => println!("[result] Encrypted text:\n{}", encrypted),
-                    Err(e) => println!("[error] Encryption failed:\n{}", e),
-                },
-                Err(e) => println!("[error] Encryption failed:\n{}", e),
-            }
+/// 一次加解密实际使用的（归一化后的）参数说明，供 `--explain` 输出
+///
+/// 例如凯撒密码的密钥会对 26 取模，`29` 与 `3` 的效果完全相同，
+/// 这一点常常让用户感到意外，因此在这里明确地展示出来。
+#[derive(Debug, PartialEq)]
+struct Explanation {
+    summary: String,
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[explain] {}", self.summary)
+    }
+}
+
+/// 一次成功的加解密结果
+struct Outcome {
+    /// 加密/解密得到的文本
+    output: String,
+    /// 本次操作所用参数的说明，供 `--explain` 输出
+    explanation: Option<Explanation>,
+}
+
+/// 根据运行时选项展示结果的附加信息（参数说明、复制到剪贴板）
+fn report_outcome(outcome: &Outcome, run: &RunOptions) {
+    if run.explain
+        && let Some(explanation) = &outcome.explanation
+    {
+        println!("{}", explanation);
+    }
+    if run.copy {
+        copy_to_clipboard(&outcome.output);
+    }
+}
+
+/// 将文本复制到系统剪贴板，成功时返回 `true`
+#[cfg(feature = "clipboard")]
+fn copy_to_clipboard(text: &str) -> bool {
+    match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text)) {
+        Ok(()) => {
+            info!("Result copied to clipboard.");
+            true
         }
-        _ => {
-            println!("[error] Algorithm not implemented yet!");
+        Err(e) => {
+            warning!("Failed to copy to clipboard: {}", e);
+            false
         }
     }
 }
 
-/// 执行解密操作
-fn execute_decrypt(algorithm: Algorithm, text: &str, key: &Option<String>) {
-    match algorithm {
-        Algorithm::Caesar => {
-            let shift = parse_caesar_key(key);
-            let cipher = caesar::Caesar::new(shift);
-            match cipher.decrypt(text) {
-                Ok(decrypted) => println!("[result] Decrypted text:\n{}", decrypted),
-                Err(e) => println!("[error] Decryption failed:\n{}", e),
-            }
-        }
-        Algorithm::Rot13 => {
-            let shift = 13;
-            let cipher = caesar::Caesar::new(shift);
-            match cipher.decrypt(text) {
-                Ok(decrypted) => println!("[result] Decrypted text:\n{}", decrypted),
-                Err(e) => println!("[error] Decryption failed:\n{}", e),
+/// 未启用 clipboard feature 时的占位实现：只打印警告，不做任何事
+#[cfg(not(feature = "clipboard"))]
+fn copy_to_clipboard(_text: &str) -> bool {
+    warning!(
+        "Clipboard support is not available in this build (rebuild with `--features clipboard`)."
+    );
+    false
+}
+
+/// 根据算法与密钥构造对应的加密引擎
+///
+/// CLI 模式、交互模式与批量处理共用这一处映射，统一以 `Box<dyn Cipher>` 返回。
+/// `options` 中与当前算法无关的选项会被忽略；XOR 专用的 `--checksum`、`--timing-safe`、`--input-encoding` 例外，
+/// 用于其他算法时报错，以免用户误以为密文带有完整性校验、隐藏了长度或采用了指定的编码。
+/// 指定了 `--cipher` 时忽略 `algorithm`，改为按名称从 [`ciphery::registry`] 构造。
+fn build_cipher(
+    algorithm: Algorithm,
+    key: &Option<String>,
+    options: &CipherOptions,
+) -> Result<Box<dyn Cipher>, CipherError> {
+    let xor_only = [
+        ("--checksum", options.checksum),
+        ("--timing-safe", options.timing_safe.is_some()),
+        ("--input-encoding", options.input_encoding.is_some()),
+    ];
+    if algorithm != Algorithm::Xor
+        && let Some((flag, _)) = xor_only.iter().find(|(_, enabled)| *enabled)
+    {
+        return Err(CipherError::InvalidInput(format!(
+            "{} is only supported for XOR",
+            flag
+        )));
+    }
+
+    let cipher: Box<dyn Cipher> = match &options.cipher {
+        // `--cipher`：按名称从注册表构造，密钥的解析与校验交给注册的构造函数
+        Some(name) => ciphery::registry::build(name, key.as_deref())?,
+        None => match algorithm {
+            Algorithm::Caesar => Box::new(
+                caesar::Caesar::new(parse_caesar_key(key)?)
+                    .with_strict(options.suppress_passthrough)
+                    .with_safe_charset(options.safe_charset),
+            ),
+            Algorithm::Rot13 => {
+                Box::new(caesar::Caesar::rot13().with_strict(options.suppress_passthrough))
             }
-        }
-        Algorithm::Vigenere => {
-            let key = key.as_ref().unwrap();
-            let cipher = vigenere::Vigenere::new(key);
-            match cipher.decrypt(text) {
-                Ok(decrypted) => println!("[result] Decrypted text:\n{}", decrypted),
-                Err(e) => println!("[error] Decryption failed:\n{}", e),
+            Algorithm::Vigenere => Box::new(
+                vigenere::Vigenere::new(require_key(key, algorithm)?)?
+                    .with_strict(options.suppress_passthrough)
+                    .with_count_non_letters(options.count_non_letters)
+                    .with_safe_charset(options.safe_charset),
+            ),
+            Algorithm::Beaufort => Box::new(
+                beaufort::Beaufort::new(require_key(key, algorithm)?)?
+                    .with_strict(options.suppress_passthrough)
+                    .with_count_non_letters(options.count_non_letters),
+            ),
+            Algorithm::Xor => Box::new(build_xor(key, options)?),
+            Algorithm::RailFence => {
+                Box::new(rail_fence::RailFence::new(parse_rail_fence_key(key)?)?)
             }
-        }
-        Algorithm::Xor => {
-            let key = key.as_ref().unwrap();
-            let cipher = xor::Xor::new(key);
-            match cipher.decrypt(text) {
-                Ok(decrypted) => println!("[result] Decrypted text:\n{}", decrypted),
-                Err(e) => println!("[error] Decryption failed:\n{}", e),
+            Algorithm::Columnar => Box::new(columnar::Columnar::with_options(
+                require_key(key, algorithm)?,
+                options.columns.unwrap_or_default(),
+                options.fill.unwrap_or_default(),
+            )?),
+            Algorithm::Morse => Box::new(morse::Morse::new()),
+            Algorithm::Nato => Box::new(nato::Nato::new()),
+            Algorithm::Playfair => Box::new(playfair::Playfair::with_merge(
+                require_key(key, algorithm)?,
+                options.merge.unwrap_or_default(),
+            )?),
+            Algorithm::Polybius => Box::new(polybius::Polybius::with_merge(
+                options.merge.unwrap_or_default(),
+            )),
+            Algorithm::Reverse => Box::new(reverse::Reverse::new()),
+            Algorithm::ReverseWords => Box::new(reverse::Reverse::words()),
+            Algorithm::Atbash => {
+                Box::new(atbash::Atbash::new().with_strict(options.suppress_passthrough))
             }
-        }
-        Algorithm::RailFence => {
-            let rails = parse_rail_fence_key(key);
-            match rail_fence::RailFence::new(rails) {
-                Ok(cipher) => match cipher.decrypt(text) {
-                    Ok(decrypted) => println!("[result] Decrypted text:\n{}", decrypted),
-                    Err(e) => println!("[error] Decryption failed:\n{}", e),
-                },
-                Err(e) => println!("[error] Decryption failed:\n{}", e),
+            // 已在命令行中占位、但尚未实现的算法：返回错误交给调用方处理，而不是打印后当作成功
+            Algorithm::Base64 => {
+                return Err(CipherError::Other(format!(
+                    "algorithm {:?} not implemented",
+                    algorithm
+                )));
             }
-        }
-        _ => {
-            println!("[error] Algorithm not implemented yet!");
-        }
+        },
+    };
+
+    #[cfg(feature = "regex")]
+    let cipher: Box<dyn Cipher> = match &options.only_matching {
+        Some(pattern) => Box::new(ciphery::only_matching::OnlyMatching::new(cipher, pattern)?),
+        None => cipher,
+    };
+
+    // 规范化放在最外层，正则看到的也是规范化后的文本
+    #[cfg(feature = "normalize")]
+    let cipher: Box<dyn Cipher> = if options.normalize {
+        Box::new(ciphery::normalize::Normalized::new(cipher))
+    } else {
+        cipher
+    };
+
+    Ok(cipher)
+}
+
+/// 按 `--timing-safe` 与 `--input-encoding` 构造 XOR 引擎
+fn build_xor(key: &Option<String>, options: &CipherOptions) -> Result<xor::Xor, CipherError> {
+    let mut cipher = xor::Xor::new(require_key(key, Algorithm::Xor)?)?;
+    match options.input_encoding {
+        Some(InputEncoding::Hex) => cipher = cipher.with_encoding(xor::Encoding::Hex),
+        Some(InputEncoding::Base64) => cipher = cipher.with_encoding(xor::Encoding::Base64),
+        Some(InputEncoding::Raw) | None => {}
+    }
+    if let Some(block_size) = options.timing_safe {
+        cipher = cipher.with_block_size(block_size)?;
+    }
+    if options.checksum {
+        cipher = cipher.with_checksum();
     }
+    Ok(cipher)
+}
+
+/// 执行加密操作
+///
+/// 成功时返回结果与参数说明；无法构造加密引擎（如算法尚未实现、密钥非法）或加密失败时返回错误。
+/// 启用 `--verify` 时，打印结果前先用 [`verify_roundtrip`] 确认密文能还原出原文；
+/// 启用 `--group` 时，输出按 [`group_text`] 分组。
+fn execute_encrypt(
+    algorithm: Algorithm,
+    text: &str,
+    key: &Option<String>,
+    options: &CipherOptions,
+    run: &RunOptions,
+) -> Result<Outcome, CipherError> {
+    let cipher = build_cipher(algorithm, key, options)?;
+    let mut encrypted = cipher.encrypt(text)?;
+    if run.verify {
+        verify_roundtrip(cipher.as_ref(), text, &encrypted)?;
+    }
+    if let Some(size) = run.group {
+        encrypted = group_text(&encrypted, size.get());
+    }
+    if run.armor {
+        encrypted = armor::armor(&algorithm_name(algorithm), &encrypted);
+    }
+    println!("[result] Encrypted text:\n{}", encrypted);
+    Ok(Outcome {
+        explanation: explain(algorithm, text, key, options),
+        output: encrypted,
+    })
+}
+
+/// `--verify`：解密刚得到的密文，确认与原文完全一致
+///
+/// 不一致（或解密本身失败）时返回 `CipherError::Other("roundtrip verification failed")`，
+/// 说明当前配置是有损的，或者算法实现存在问题。
+fn verify_roundtrip(
+    cipher: &dyn Cipher,
+    plaintext: &str,
+    ciphertext: &str,
+) -> Result<(), CipherError> {
+    ensure_decryptable(cipher)?;
+    match cipher.decrypt(ciphertext) {
+        Ok(decrypted) if decrypted == plaintext => Ok(()),
+        _ => Err(CipherError::Other(
+            "roundtrip verification failed".to_string(),
+        )),
+    }
+}
+
+/// 装甲文本块头部使用的算法名，与命令行 `--algo` 的取值相同（如 `rail-fence`）
+fn algorithm_name(algorithm: Algorithm) -> String {
+    algorithm
+        .to_possible_value()
+        .expect("every algorithm has a command-line name")
+        .get_name()
+        .to_string()
+}
+
+/// 日志中显示的算法：指定了 `--cipher` 时为注册表中的名称，否则为 `--algo` 选择的算法
+fn algorithm_label(algorithm: Algorithm, options: &CipherOptions) -> String {
+    match &options.cipher {
+        Some(name) => name.clone(),
+        None => format!("{:?}", algorithm),
+    }
+}
+
+/// 识别装甲文本块（见 [`armor`]）：是则返回头部记录的算法与正文，否则原样返回 `algorithm` 与 `text`
+///
+/// 头部记录的算法与 `algorithm` 不同时以头部为准，并打印警告，避免用户误以为 `--algo` 生效了。
+fn unarmor(algorithm: Algorithm, text: String) -> Result<(Algorithm, String), CipherError> {
+    if !armor::is_armored(&text) {
+        return Ok((algorithm, text));
+    }
+    let armored = armor::dearmor(&text)?;
+    let header_algorithm = Algorithm::from_str(&armored.algo, true).map_err(|_| {
+        CipherError::InvalidInput(format!(
+            "unknown algorithm '{}' in armored message",
+            armored.algo
+        ))
+    })?;
+    if header_algorithm == algorithm {
+        info!(
+            "Armored message: using {:?} from the header",
+            header_algorithm
+        );
+    } else {
+        warning!(
+            "Armored message was encrypted with {:?}; using it instead of the selected {:?}",
+            header_algorithm,
+            algorithm
+        );
+    }
+    Ok((header_algorithm, armored.body))
+}
+
+/// 单向变换（[`Cipher::supports_decrypt`] 为 `false`）无法解密，直接拒绝，而不是交给算法自行报错
+fn ensure_decryptable(cipher: &dyn Cipher) -> Result<(), CipherError> {
+    if cipher.supports_decrypt() {
+        Ok(())
+    } else {
+        Err(CipherError::Other(
+            "this algorithm does not support decryption".to_string(),
+        ))
+    }
+}
+
+/// `--group`：去掉所有空白后，每 `size` 个字符插入一个空格，即传统的"五字一组"写法
+fn group_text(text: &str, size: usize) -> String {
+    let chars: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    chars
+        .chunks(size)
+        .map(|chunk| chunk.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 去掉分组书写的密文中的所有空白，供 `decrypt --grouped` 使用
+fn strip_grouping(text: &str) -> String {
+    text.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// 执行解密操作
+///
+/// 成功时返回结果与参数说明；无法构造加密引擎（如算法尚未实现、密钥非法）或解密失败时返回错误
+fn execute_decrypt(
+    algorithm: Algorithm,
+    text: &str,
+    key: &Option<String>,
+    options: &CipherOptions,
+) -> Result<Outcome, CipherError> {
+    let cipher = build_cipher(algorithm, key, options)?;
+    ensure_decryptable(cipher.as_ref())?;
+    let decrypted = cipher.decrypt(text)?;
+    println!("[result] Decrypted text:\n{}", decrypted);
+    Ok(Outcome {
+        // 与 `report_key_coverage` 一致，按明文计算（XOR 密文经过 hex / base64 编码，长度会翻倍）
+        explanation: explain(algorithm, &decrypted, key, options),
+        output: decrypted,
+    })
+}
+
+/// 以原始字节读取输入文件并直接异或解密（`--input-encoding raw`，仅支持 XOR）
+///
+/// 原始字节无法通过 `--text` 传入，因此必须且只能提供一个 `--file-path`。
+fn execute_decrypt_raw(
+    algorithm: Algorithm,
+    text: &Option<String>,
+    file_paths: &[String],
+    key: &Option<String>,
+    run: &RunOptions,
+    options: &CipherOptions,
+) -> Result<Outcome, CipherError> {
+    if algorithm != Algorithm::Xor {
+        return Err(CipherError::InvalidInput(
+            "--input-encoding raw is only supported for XOR".to_string(),
+        ));
+    }
+    let [path] = file_paths else {
+        return Err(CipherError::InvalidInput(
+            "--input-encoding raw requires exactly one --file-path".to_string(),
+        ));
+    };
+    if text.is_some() {
+        return Err(CipherError::InvalidInput(
+            "--input-encoding raw reads raw bytes from --file-path and cannot be combined with --text"
+                .to_string(),
+        ));
+    }
+
+    let cipher = build_xor(key, options)?;
+    info!("Reading raw bytes from file: {}", path);
+    let bytes = read_input_bytes(path, run)?;
+    let decrypted = cipher.decrypt_bytes(&bytes)?;
+    println!("[result] Decrypted text:\n{}", decrypted);
+    Ok(Outcome {
+        explanation: key.as_ref().map(|k| Explanation {
+            summary: format!("key of {} bytes cycled over {} bytes", k.len(), bytes.len()),
+        }),
+        output: decrypted,
+    })
+}
+
+/// 根据算法与原始密钥生成参数说明，`text` 为明文（加密的输入或解密的输出）
+fn explain(
+    algorithm: Algorithm,
+    text: &str,
+    key: &Option<String>,
+    options: &CipherOptions,
+) -> Option<Explanation> {
+    // 注册表中的算法没有可供解释的参数
+    if options.cipher.is_some() {
+        return None;
+    }
+    let summary = match algorithm {
+        Algorithm::Caesar => format!(
+            "effective shift: {} (from key {})",
+            parse_caesar_key(key).ok()?,
+            key.as_ref()?
+        ),
+        Algorithm::Rot13 => "effective shift: 13 (fixed)".to_string(),
+        Algorithm::Vigenere | Algorithm::Beaufort => {
+            let key_len = key.as_ref()?.len();
+            let letters = text.chars().filter(|c| c.is_ascii_alphabetic()).count();
+            format!(
+                "key repeated {} times over {} letters",
+                letters.div_ceil(key_len),
+                letters
+            )
+        }
+        Algorithm::Xor => format!(
+            "key of {} bytes cycled over {} bytes",
+            key.as_ref()?.len(),
+            text.len()
+        ),
+        Algorithm::RailFence => format!(
+            "{} rails over {} characters",
+            parse_rail_fence_key(key).ok()?,
+            text.chars().count()
+        ),
+        _ => return None,
+    };
+    Some(Explanation { summary })
 }
 
 // ====== 辅助工具函数 ======
 /// 解析输入文本：优先使用命令行直接输入的 text，其次从文件路径读取
-fn resolve_input_text(text: &Option<String>, file_path: &Option<String>) -> Option<String> {
+fn resolve_input_text(
+    text: &Option<String>,
+    file_paths: &[String],
+    run: &RunOptions,
+) -> Result<String, CipherError> {
     if let Some(t) = text {
-        println!("[info] Input text: {}", t);
-        Some(t.clone())
-    } else if let Some(fp) = file_path {
-        println!("[info] Reading text from file: {}", fp);
-        match fs::read_to_string(fp) {
-            Ok(content) => Some(content),
-            Err(e) => {
-                println!("[error] Failed to read file: {}", e);
-                None
-            }
-        }
+        info!("Input text: {}", t);
+        Ok(t.clone())
+    } else if file_paths.len() > 1 {
+        Err(CipherError::InvalidInput(
+            "Multiple input files are only supported for batch encrypt/decrypt!".to_string(),
+        ))
+    } else if let Some(fp) = file_paths.first() {
+        info!("Reading text from file: {}", fp);
+        read_input_file(fp, run)
+    } else {
+        Err(CipherError::InvalidInput(
+            "No text or file path provided!".to_string(),
+        ))
+    }
+}
+
+/// 按运行时选项读取输入文件：先检查文件大小，再按需显示进度
+fn read_input_file(path: &str, run: &RunOptions) -> Result<String, CipherError> {
+    check_file_size(path, run.max_file_size)?;
+    if run.progress {
+        read_file_with_progress(path)
     } else {
-        println!("[error] No text or file path provided!");
-        None
+        read_file(path)
     }
 }
 
+/// 与 [`read_input_file`] 相同，但按原始字节读取，不要求内容是合法 UTF-8
+fn read_input_bytes(path: &str, run: &RunOptions) -> Result<Vec<u8>, CipherError> {
+    check_file_size(path, run.max_file_size)?;
+    if run.progress {
+        read_bytes_with_progress(path)
+    } else {
+        fs::read(path).map_err(|e| CipherError::IoError {
+            path: path.to_string(),
+            kind: e.kind(),
+        })
+    }
+}
+
+/// 在读取前根据元数据检查文件大小，超过 `limit` 字节时返回 `CipherError::InvalidInput`
+///
+/// 整个文件会被一次性读入内存，这一检查可以避免误把数 GB 的文件交给加密引擎。
+fn check_file_size(path: &str, limit: u64) -> Result<(), CipherError> {
+    let size = fs::metadata(path)
+        .map_err(|e| CipherError::IoError {
+            path: path.to_string(),
+            kind: e.kind(),
+        })?
+        .len();
+    if size > limit {
+        return Err(CipherError::InvalidInput(format!(
+            "file '{}' is {} bytes, which exceeds --max-file-size ({} bytes); \
+             raise the limit or split the file into smaller pieces",
+            path, size, limit
+        )));
+    }
+    Ok(())
+}
+
+/// 读取文件内容，失败时将路径与 `io::ErrorKind` 一并放入 `CipherError::IoError`
+fn read_file(path: &str) -> Result<String, CipherError> {
+    fs::read_to_string(path).map_err(|e| CipherError::IoError {
+        path: path.to_string(),
+        kind: e.kind(),
+    })
+}
+
+/// 粘贴模式的结束标记：单独一行的 "."
+const PASTE_SENTINEL: &str = ".";
+
+/// 逐行读取多行文本，直到遇到单独一行的结束标记或 EOF，各行以 `\n` 连接
+fn read_multiline<R: BufRead>(reader: R) -> io::Result<String> {
+    let mut lines = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line == PASTE_SENTINEL {
+            break;
+        }
+        lines.push(line);
+    }
+    Ok(lines.join("\n"))
+}
+
+/// 分块读取文件，并在文件较大且 stderr 为终端时显示读取进度
+fn read_file_with_progress(path: &str) -> Result<String, CipherError> {
+    let bytes = read_bytes_with_progress(path)?;
+
+    // 与 fs::read_to_string 保持一致：非 UTF-8 内容视为 InvalidData
+    String::from_utf8(bytes).map_err(|_| CipherError::IoError {
+        path: path.to_string(),
+        kind: io::ErrorKind::InvalidData,
+    })
+}
+
+/// 分块读取文件的原始字节，并在文件较大且 stderr 为终端时显示读取进度
+fn read_bytes_with_progress(path: &str) -> Result<Vec<u8>, CipherError> {
+    let io_error = |e: io::Error| CipherError::IoError {
+        path: path.to_string(),
+        kind: e.kind(),
+    };
+
+    let file = File::open(path).map_err(io_error)?;
+    let total = file.metadata().map_err(io_error)?.len();
+    let enabled = progress::should_show(total, io::stderr().is_terminal());
+
+    let mut progress = Progress::new(io::stderr(), total, enabled);
+    progress::read_with_progress(file, &mut progress).map_err(io_error)
+}
+
 /// 校验密钥是否已提供（对于需要密钥的算法）
+///
+/// 同时提供了输入文本时，还会用 [`key_warnings`] 检查常见的参数误用并打印 `[warning]`，但不会中止执行。
 // 注意，这里的 key 还是从 CLI 解析到的，其类型当然还是 &Option<String>
-fn validate_key(key: &Option<String>, algorithm: Algorithm) -> bool {
+fn validate_key(
+    key: &Option<String>,
+    algorithm: Algorithm,
+    options: &CipherOptions,
+    text: Option<&str>,
+) -> Result<(), CipherError> {
     if let Some(k) = key {
-        println!("[info] Key used: {}", k);
-        true
+        for warning in key_warnings(k, text) {
+            warning!("{}", warning);
+        }
+        if algorithm == Algorithm::RailFence
+            && let Some(warning) = text.and_then(|text| {
+                let cipher = rail_fence::RailFence::new(k.trim().parse().ok()?).ok()?;
+                rail_fence_warning(&cipher, text)
+            })
+        {
+            warning!("{}", warning);
+        }
+        if algorithm == Algorithm::Columnar
+            && let Some(warning) = text.and_then(|text| columnar_warning(k, options, text))
+        {
+            warning!("{}", warning);
+        }
+        return Ok(());
+    }
+    // 注册表中的算法是否需要密钥，由其构造函数自行判断
+    if options.cipher.is_some() {
+        return Ok(());
+    }
+    // 根据算法判断是否必须提供密钥
+    match algorithm {
+        Algorithm::Caesar
+        | Algorithm::Vigenere
+        | Algorithm::Beaufort
+        | Algorithm::Playfair
+        | Algorithm::Xor
+        | Algorithm::RailFence
+        | Algorithm::Columnar => require_key(key, algorithm).map(|_| ()),
+        // ROT13 / Base64 等不需要密钥的算法可以在这里放行
+        _ => Ok(()),
+    }
+}
+
+/// 密钥长度达到该值且原样出现在输入文本中时，怀疑用户把文本当成了密钥
+const SUSPICIOUS_KEY_LEN: usize = 16;
+
+/// 检查密钥是否可疑，返回需要提示给用户的警告
+///
+/// 这些启发式规则用于发现常见的参数顺序错误（例如把 `--text` 与 `--key` 的值写反）：
+/// * 去掉首尾空白后密钥为空；
+/// * 密钥与输入文本完全相同；
+/// * 较长的密钥原样出现在输入文本中。
+fn key_warnings(key: &str, text: Option<&str>) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let key = key.trim();
+    if key.is_empty() {
+        warnings.push("the key is empty after trimming whitespace".to_string());
+        return warnings;
+    }
+
+    if let Some(text) = text {
+        let text = text.trim();
+        if key == text {
+            warnings.push(
+                "the key is identical to the input text; did you swap --key and --text?"
+                    .to_string(),
+            );
+        } else if key.chars().count() >= SUSPICIOUS_KEY_LEN && text.contains(key) {
+            warnings.push(
+                "the key appears verbatim in the input text; did you pass the text as the key?"
+                    .to_string(),
+            );
+        }
+    }
+    warnings
+}
+
+/// 栅栏层数不少于文本字符数时返回提示（见 [`rail_fence::RailFence`] 的说明）
+///
+/// 从第 0 层向下出发时文本原样输出；只有从中途层出发、确实发生了重排时，才提示"几乎没有打乱"。
+fn rail_fence_warning(cipher: &rail_fence::RailFence, text: &str) -> Option<String> {
+    let rails = cipher.rails();
+    let len = text.chars().count();
+    if len <= 1 || rails < len {
+        return None;
+    }
+    let effect = if cipher.leaves_unchanged(len) {
+        "leaves the text unchanged"
     } else {
-        // 根据算法判断是否必须提供密钥
-        match algorithm {
-            Algorithm::Caesar => {
-                println!("[error] No key provided for Caesar cipher!");
-                false
-            }
-            Algorithm::Vigenere => {
-                println!("[error] No key provided for Vigenere cipher!");
-                false
-            }
-            Algorithm::RailFence => {
-                println!("[error] No key provided for Rail Fence cipher!");
-                false
-            }
-            // ROT13 / Base64 等不需要密钥的算法可以在这里放行
-            _ => true,
+        "barely scrambles the text"
+    };
+    Some(format!(
+        "{} rails over {} characters: every rail holds at most one character, which {}",
+        rails, len, effect
+    ))
+}
+
+/// 列换位不改变任何字符的位置时（单字母关键词，或已按字母排序的关键词遇到单行文本、逐列写入），返回提示
+fn columnar_warning(key: &str, options: &CipherOptions, text: &str) -> Option<String> {
+    let cipher = columnar::Columnar::with_options(
+        key,
+        options.columns.unwrap_or_default(),
+        options.fill.unwrap_or_default(),
+    )
+    .ok()?;
+    let len = text.chars().count();
+    (len > 1 && cipher.leaves_unchanged(len)).then(|| {
+        format!(
+            "the columnar transposition leaves all {} characters in place; use a longer keyword whose letters are not in alphabetical order",
+            len
+        )
+    })
+}
+
+/// 密钥长度低于文本长度的这一比例时，提示密钥重复使用的风险
+const MIN_KEY_COVERAGE: f64 = 0.1;
+
+/// [`key_coverage`] 的结果
+struct KeyCoverage {
+    /// 密钥与文本的长度比，作为 `[info]` 输出
+    ratio: String,
+    /// 密钥远短于文本时的警告
+    warning: Option<String>,
+}
+
+/// 打印 [`key_coverage`] 的结果；`plaintext` 必须是明文（加密的输入或解密的输出），而不是密文
+fn report_key_coverage(algorithm: Algorithm, key: &Option<String>, plaintext: &str) {
+    if let Some(coverage) = key
+        .as_deref()
+        .and_then(|k| key_coverage(algorithm, k, plaintext))
+    {
+        info!("{}", coverage.ratio);
+        if let Some(warning) = coverage.warning {
+            warning!("{}", warning);
         }
     }
 }
 
-/// 解析凯撒密码的密钥（从 String 转为 u8 偏移量）
-fn parse_caesar_key(key: &Option<String>) -> u8 {
-    let shift: u8 = key
-        .as_ref() // 从 &Option<String> => Option<&String>
-        .unwrap() // Option<&String> => &String
-        .parse() // &String => Result<u8, ParseIntError>
-        .expect("Key for Caesar cipher must be a number!");
-    shift % 26
+/// 周期性密钥算法（Vigenere / Beaufort / XOR）：报告密钥与文本的长度比
+///
+/// 密钥不短于文本时不会重复使用，接近一次性密码本；密钥越短，重复次数越多，
+/// 越容易被 Kasiski 检验等频率分析破解。Vigenere / Beaufort 按字母计数，XOR 按字节计数。
+/// 其他算法或文本为空时返回 `None`。
+fn key_coverage(algorithm: Algorithm, key: &str, text: &str) -> Option<KeyCoverage> {
+    let (key_len, text_len, unit) = match algorithm {
+        Algorithm::Vigenere | Algorithm::Beaufort => (
+            key.chars().filter(|c| c.is_ascii_alphabetic()).count(),
+            text.chars().filter(|c| c.is_ascii_alphabetic()).count(),
+            "letters",
+        ),
+        Algorithm::Xor => (key.len(), text.len(), "bytes"),
+        _ => return None,
+    };
+    if key_len == 0 || text_len == 0 {
+        return None;
+    }
+
+    let ratio = key_len as f64 / text_len as f64;
+    let warning = (ratio < MIN_KEY_COVERAGE).then(|| {
+        format!(
+            "the key is much shorter than the text ({} vs {} {}) and repeats about {} times; repeated keys are vulnerable to frequency analysis",
+            key_len,
+            text_len,
+            unit,
+            text_len.div_ceil(key_len)
+        )
+    });
+    Some(KeyCoverage {
+        ratio: format!(
+            "Key-to-text length ratio: {}/{} {} ({:.2})",
+            key_len, text_len, unit, ratio
+        ),
+        warning,
+    })
+}
+
+/// 解析最终使用的密钥：`--key-stdin` 时从标准输入读取，`--daily` 时由日期推导，否则使用 `-k`
+///
+/// 启用 `--normalize` 时，得到的密钥还会规范化为 NFC 形式。
+fn resolve_key(
+    algorithm: Algorithm,
+    key: &Option<String>,
+    options: &CipherOptions,
+) -> Result<Option<String>, CipherError> {
+    let key = if options.key_stdin {
+        Some(read_key_masked()?)
+    } else {
+        daily_key(algorithm, key, options)?
+    };
+    #[cfg(feature = "normalize")]
+    let key = match key {
+        Some(k) if options.normalize => Some(ciphery::normalize::nfc(&k)),
+        key => key,
+    };
+    // 密钥本身从不写入日志（启用 `log` feature 时日志可能落盘），只记录来源与长度，
+    // 格式与库中各算法的 Debug 输出一致
+    if let Some(k) = &key {
+        let source = if options.key_stdin {
+            "read from stdin"
+        } else {
+            "used"
+        };
+        info!("Key {}: <redacted, len={}>", source, k.chars().count());
+    }
+    Ok(key)
+}
+
+/// 读取密钥且不回显：stdin 为终端时使用 `dialoguer::Password` 掩码输入，否则读取管道中的第一行
+fn read_key_masked() -> Result<String, CipherError> {
+    let result = if io::stdin().is_terminal() {
+        Password::new()
+            .with_prompt("Key")
+            .interact()
+            .map_err(|e| e.to_string())
+    } else {
+        read_key_line(io::stdin().lock()).map_err(|e| e.to_string())
+    };
+    result.map_err(|e| CipherError::InvalidKey(format!("Failed to read key from stdin: {}", e)))
+}
+
+/// 从 `reader` 读取一行作为密钥，去掉行尾的换行符（`\n` 或 `\r\n`），其余字符原样保留
+fn read_key_line<R: BufRead>(mut reader: R) -> io::Result<String> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let key = line.strip_suffix('\n').unwrap_or(&line);
+    let key = key.strip_suffix('\r').unwrap_or(key);
+    Ok(key.to_string())
+}
+
+/// 启用 `--daily` 时，用由日期推导出的偏移量代替密钥（仅 Caesar），否则原样返回密钥
+#[cfg(feature = "daily")]
+fn daily_key(
+    algorithm: Algorithm,
+    key: &Option<String>,
+    options: &CipherOptions,
+) -> Result<Option<String>, CipherError> {
+    if !options.daily {
+        return Ok(key.clone());
+    }
+    if algorithm != Algorithm::Caesar {
+        return Err(CipherError::InvalidInput(
+            "--daily is only supported for Caesar cipher!".to_string(),
+        ));
+    }
+    let date = options
+        .date
+        .unwrap_or_else(|| chrono::Local::now().date_naive());
+    let shift = caesar::daily_shift(date);
+    info!("Daily shift for {}: {}", date, shift);
+    Ok(Some(shift.to_string()))
+}
+
+/// 未启用 `daily` feature 时密钥原样返回
+#[cfg(not(feature = "daily"))]
+fn daily_key(
+    _algorithm: Algorithm,
+    key: &Option<String>,
+    _options: &CipherOptions,
+) -> Result<Option<String>, CipherError> {
+    Ok(key.clone())
+}
+
+/// 取出必需的密钥，未提供时返回 `CipherError::InvalidKey`
+fn require_key(key: &Option<String>, algorithm: Algorithm) -> Result<&str, CipherError> {
+    key.as_deref().ok_or_else(|| {
+        CipherError::InvalidKey(format!("No key provided for {:?} cipher!", algorithm))
+    })
+}
+
+/// 解析凯撒密码的密钥（从 String 转为 u8 偏移量）并对 26 取模
+///
+/// 规则见 [`caesar::parse_key`]：数字，或按 A=0 换算的单个字母，例如 `-k D` 与 `-k 3` 等价。
+fn parse_caesar_key(key: &Option<String>) -> Result<u8, CipherError> {
+    let key = require_key(key, Algorithm::Caesar)?;
+    Ok((caesar::parse_key(key)? % 26) as u8)
 }
 
 /// 解析 Rail Fence 的密钥（从 String 转为 usize 栅栏层数）
-fn parse_rail_fence_key(key: &Option<String>) -> usize {
-    key
-        .as_ref()
-        .unwrap()
+fn parse_rail_fence_key(key: &Option<String>) -> Result<usize, CipherError> {
+    require_key(key, Algorithm::RailFence)?
         .parse()
-        .expect("Key for Rail Fence cipher must be a number >= 2!")
+        .map_err(|_| {
+            CipherError::InvalidKey("Key for Rail Fence cipher must be a number >= 2!".to_string())
+        })
 }
 
 /// 程序结束时打印信息
 fn print_exit_message() {
-    println!("[info] Thanks for using Ciphery! Goodbye! 👋\n");
+    info!("Thanks for using Ciphery! Goodbye! 👋\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::num::NonZeroUsize;
+
+    #[test]
+    fn test_read_nonexistent_file_reports_not_found() {
+        let path = "definitely/not/a/real/file.txt";
+        let err =
+            resolve_input_text(&None, &[path.to_string()], &RunOptions::default()).unwrap_err();
+        assert_eq!(
+            err,
+            CipherError::IoError {
+                path: path.to_string(),
+                kind: io::ErrorKind::NotFound,
+            }
+        );
+    }
+
+    #[test]
+    fn test_show_scores_lists_every_caesar_shift() {
+        let candidates = caesar::top_candidates("Wkh txlfn eurzq ira mxpsv ryhu wkh odcb grj", 26);
+        let lines = format_scored(candidates.iter().map(|(shift, candidate, score)| {
+            (format!("shift {:>2}", shift), *score, candidate.as_str())
+        }));
+        assert_eq!(lines.len(), 26);
+        assert!(lines.iter().all(|line| line.contains("(score ")));
+        assert!(lines[0].starts_with("shift  3 (score"));
+        assert!(lines[0].ends_with("The quick brown fox jumps over the lazy dog"));
+    }
+
+    #[test]
+    fn test_key_warnings_flag_suspicious_keys() {
+        assert_eq!(key_warnings("   ", Some("hello")).len(), 1);
+        assert_eq!(key_warnings("", None).len(), 1);
+
+        let swapped = key_warnings("ATTACK AT DAWN", Some("ATTACK AT DAWN\n"));
+        assert_eq!(swapped.len(), 1);
+        assert!(swapped[0].contains("identical"));
+
+        let long_key = "meet me at the old bridge";
+        let warnings = key_warnings(long_key, Some("Note: meet me at the old bridge tonight"));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("verbatim"));
+    }
+
+    #[test]
+    fn test_key_warnings_ignore_normal_keys() {
+        assert!(key_warnings("LEMON", Some("ATTACK AT DAWN")).is_empty());
+        assert!(key_warnings("3", Some("hello 3 worlds")).is_empty());
+        assert!(key_warnings("secret", None).is_empty());
+        // 短密钥出现在文本中很常见，不应报警
+        assert!(key_warnings("the", Some("the quick brown fox")).is_empty());
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn test_algorithm_selection_is_logged() {
+        use std::sync::Mutex;
+
+        /// 把日志记录收集到内存中，供断言使用
+        struct TestLogger(Mutex<Vec<(log::Level, String)>>);
+
+        impl log::Log for TestLogger {
+            fn enabled(&self, _metadata: &log::Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, record: &log::Record) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((record.level(), record.args().to_string()));
+            }
+
+            fn flush(&self) {}
+        }
+
+        static LOGGER: TestLogger = TestLogger(Mutex::new(Vec::new()));
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Info);
+
+        handle_encrypt(
+            &Some("hello".to_string()),
+            &Algorithm::Caesar,
+            &Some("3".to_string()),
+            &[],
+            &RunOptions::default(),
+            &CipherOptions::default(),
+        )
+        .unwrap();
+
+        let records = LOGGER.0.lock().unwrap();
+        assert!(
+            records.iter().any(
+                |(level, message)| *level == log::Level::Info && message == "Algorithm: Caesar"
+            )
+        );
+    }
+
+    #[test]
+    fn test_rail_fence_warning_for_short_text() {
+        let rails = |n| rail_fence::RailFence::new(n).unwrap();
+        assert_eq!(
+            rail_fence_warning(&rails(5), "abc").unwrap(),
+            "5 rails over 3 characters: every rail holds at most one character, which leaves the text unchanged"
+        );
+        assert!(rail_fence_warning(&rails(3), "abc").is_some());
+        assert!(rail_fence_warning(&rails(2), "abc").is_none());
+        assert!(rail_fence_warning(&rails(5), "a").is_none());
+
+        // 从中途层向上出发时确实发生了重排，只提示打乱程度有限
+        let offset = rail_fence::RailFence::with_offset(5, 3, rail_fence::Direction::Up).unwrap();
+        assert!(
+            rail_fence_warning(&offset, "abc")
+                .unwrap()
+                .ends_with("which barely scrambles the text")
+        );
+    }
+
+    #[test]
+    fn test_columnar_warning_for_identity_keywords() {
+        let options = CipherOptions::default();
+        assert!(columnar_warning("K", &options, "HELLO").is_some());
+        assert!(columnar_warning("ABC", &options, "HEY").is_some());
+        assert!(columnar_warning("ABC", &options, "HELLO WORLD").is_none());
+        assert!(columnar_warning("ZEBRAS", &options, "HEY").is_none());
+
+        let options = CipherOptions {
+            fill: Some(columnar::WriteOrder::ColumnMajor),
+            ..CipherOptions::default()
+        };
+        assert!(columnar_warning("ABC", &options, "HELLO WORLD").is_some());
+    }
+
+    #[test]
+    fn test_key_coverage_warns_for_short_keys() {
+        let text = "a".repeat(100);
+        let short = key_coverage(Algorithm::Vigenere, "KY", &text).unwrap();
+        assert_eq!(
+            short.ratio,
+            "Key-to-text length ratio: 2/100 letters (0.02)"
+        );
+        assert!(short.warning.unwrap().contains("repeats about 50 times"));
+
+        let long_key = "b".repeat(100);
+        let long = key_coverage(Algorithm::Vigenere, &long_key, &text).unwrap();
+        assert_eq!(
+            long.ratio,
+            "Key-to-text length ratio: 100/100 letters (1.00)"
+        );
+        assert!(long.warning.is_none());
+        assert!(
+            key_coverage(Algorithm::Xor, &"k".repeat(200), &text)
+                .unwrap()
+                .warning
+                .is_none()
+        );
+
+        // XOR 同样适用；不使用周期密钥的算法或空文本不做报告
+        assert!(
+            key_coverage(Algorithm::Xor, "k", &text)
+                .unwrap()
+                .warning
+                .is_some()
+        );
+        assert!(key_coverage(Algorithm::Caesar, "3", &text).is_none());
+        assert!(key_coverage(Algorithm::Vigenere, "KEY", "").is_none());
+    }
+
+    #[test]
+    fn test_key_coverage_on_decrypt_counts_plaintext_bytes() {
+        let key = Some("k".to_string());
+        let options = CipherOptions::default();
+        let ciphertext = execute_encrypt(
+            Algorithm::Xor,
+            "hello",
+            &key,
+            &options,
+            &RunOptions::default(),
+        )
+        .unwrap()
+        .output;
+        // hex 密文的长度是明文字节数的两倍，按密文计算会把比例减半
+        assert_eq!(
+            key_coverage(Algorithm::Xor, "k", &ciphertext)
+                .unwrap()
+                .ratio,
+            "Key-to-text length ratio: 1/10 bytes (0.10)"
+        );
+        let outcome = execute_decrypt(Algorithm::Xor, &ciphertext, &key, &options).unwrap();
+        assert_eq!(
+            key_coverage(Algorithm::Xor, "k", &outcome.output)
+                .unwrap()
+                .ratio,
+            "Key-to-text length ratio: 1/5 bytes (0.20)"
+        );
+    }
+
+    #[test]
+    fn test_format_xor_key() {
+        assert_eq!(format_xor_key(0x58), "0x58 ('X')");
+        assert_eq!(format_xor_key(0x00), "0x00");
+        assert_eq!(format_xor_key(b' '), "0x20");
+    }
+
+    #[test]
+    fn test_format_histogram_scales_bars() {
+        let lines = format_histogram(&analysis::letter_frequency("aaab"));
+        assert_eq!(lines.len(), 26);
+        assert_eq!(lines[0], format!("a |{} 3", "#".repeat(40)));
+        assert!(lines[1].starts_with(&format!("b |{} ", "#".repeat(14))));
+        assert!(lines[1].ends_with(" 1"));
+        assert!(lines[2].starts_with("c | "));
+    }
+
+    #[test]
+    fn test_format_histogram_handles_huge_counts() {
+        let mut counts = [0u32; 26];
+        counts[0] = u32::MAX;
+        counts[1] = u32::MAX / 2;
+        let lines = format_histogram(&counts);
+        assert_eq!(lines[0], format!("a |{} {}", "#".repeat(40), u32::MAX));
+        assert!(lines[1].starts_with(&format!("b |{} ", "#".repeat(20))));
+    }
+
+    #[test]
+    fn test_max_file_size_guard() {
+        let path = std::env::temp_dir().join(format!("ciphery-max-size-{}", std::process::id()));
+        fs::write(&path, "this file is larger than ten bytes").unwrap();
+        let path = path.to_string_lossy().to_string();
+
+        let run = RunOptions {
+            max_file_size: 10,
+            ..RunOptions::default()
+        };
+        let err = resolve_input_text(&None, std::slice::from_ref(&path), &run).unwrap_err();
+        assert!(
+            matches!(err, CipherError::InvalidInput(ref msg) if msg.contains("--max-file-size"))
+        );
+
+        // 默认上限足够大，同一个文件可以正常读取
+        assert!(
+            resolve_input_text(&None, std::slice::from_ref(&path), &RunOptions::default()).is_ok()
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_explain_large_caesar_key() {
+        let outcome = execute_encrypt(
+            Algorithm::Caesar,
+            "hello",
+            &Some("29".to_string()),
+            &CipherOptions::default(),
+            &RunOptions::default(),
+        );
+        assert_eq!(
+            outcome.unwrap().explanation.unwrap().to_string(),
+            "[explain] effective shift: 3 (from key 29)"
+        );
+    }
+
+    #[test]
+    fn test_explain_xor_decrypt_counts_plaintext_bytes() {
+        let outcome = execute_decrypt(
+            Algorithm::Xor,
+            "030e070704",
+            &Some("k".to_string()),
+            &CipherOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(outcome.output, "hello");
+        assert_eq!(
+            outcome.explanation.unwrap().summary,
+            "key of 1 bytes cycled over 5 bytes"
+        );
+    }
+
+    #[test]
+    fn test_explain_vigenere_key_repetition() {
+        let outcome = execute_encrypt(
+            Algorithm::Vigenere,
+            "ATTACK AT DAWN, DEFEND THE WALL",
+            &Some("LEMON".to_string()),
+            &CipherOptions::default(),
+            &RunOptions::default(),
+        );
+        assert_eq!(
+            outcome.unwrap().explanation.unwrap().summary,
+            "key repeated 5 times over 25 letters"
+        );
+    }
+
+    #[test]
+    fn test_verify_roundtrip() {
+        let verify = RunOptions {
+            verify: true,
+            ..RunOptions::default()
+        };
+        let outcome = execute_encrypt(
+            Algorithm::Caesar,
+            "Hello, World!",
+            &Some("3".to_string()),
+            &CipherOptions::default(),
+            &verify,
+        );
+        assert_eq!(outcome.unwrap().output, "Khoor, Zruog!");
+
+        // Morse 不区分大小写，小写原文无法原样还原
+        let err = execute_encrypt(
+            Algorithm::Morse,
+            "hello",
+            &None,
+            &CipherOptions::default(),
+            &verify,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            err,
+            CipherError::Other("roundtrip verification failed".to_string())
+        );
+
+        // 去除空格的栅栏密码同样是有损的
+        let lossy = rail_fence::RailFence::new(3)
+            .unwrap()
+            .with_preserve_spaces(false);
+        let encrypted = lossy.encrypt("attack at dawn").unwrap();
+        assert!(verify_roundtrip(&lossy, "attack at dawn", &encrypted).is_err());
+        assert!(verify_roundtrip(&lossy, "attackatdawn", &encrypted).is_ok());
+    }
+
+    #[test]
+    fn test_group_output_and_decrypt_grouped_input() {
+        let group = RunOptions {
+            group: NonZeroUsize::new(5),
+            ..RunOptions::default()
+        };
+        let key = Some("LEMON".to_string());
+        let outcome = execute_encrypt(
+            Algorithm::Vigenere,
+            "ATTACKATDAWNDEFENDTHEWALL",
+            &key,
+            &CipherOptions::default(),
+            &group,
+        )
+        .unwrap();
+        assert_eq!(outcome.output, "LXFOP VEFRN HRPSS PRPHU PAMZY");
+        assert_eq!(group_text("ab cd\nef", 2), "ab cd ef");
+
+        let cipher = build_cipher(Algorithm::Vigenere, &key, &CipherOptions::default()).unwrap();
+        assert_eq!(
+            cipher.decrypt(&strip_grouping(&outcome.output)).unwrap(),
+            "ATTACKATDAWNDEFENDTHEWALL"
+        );
+
+        // 分组后的 hex 密文含有空格，只有声明 `--grouped` 时才能解密
+        let key = Some("key".to_string());
+        let outcome = execute_encrypt(
+            Algorithm::Xor,
+            "hello",
+            &key,
+            &CipherOptions::default(),
+            &group,
+        )
+        .unwrap();
+        let text = Some(outcome.output);
+        let decrypt = |grouped| {
+            handle_decrypt(
+                &text,
+                &Algorithm::Xor,
+                &key,
+                &[],
+                &RunOptions::default(),
+                &CipherOptions::default(),
+                grouped,
+            )
+        };
+        assert!(decrypt(false).is_err());
+        assert!(decrypt(true).is_ok());
+    }
+
+    /// 单向变换：把文本替换为字节和的 hex，无法还原
+    struct Checksum;
+
+    impl Cipher for Checksum {
+        fn encrypt(&self, text: &str) -> Result<String, CipherError> {
+            let sum = text.bytes().fold(0u8, |acc, b| acc.wrapping_add(b));
+            Ok(format!("{:02x}", sum))
+        }
+
+        fn decrypt(&self, text: &str) -> Result<String, CipherError> {
+            // 故意返回"成功"，确认拒绝解密的是调用方而不是算法本身
+            Ok(text.to_string())
+        }
+
+        fn inverse(&self) -> Box<dyn Cipher> {
+            Box::new(Checksum)
+        }
+
+        fn key_requirement(&self) -> ciphery::KeyRequirement {
+            ciphery::KeyRequirement::None
+        }
+
+        fn supports_decrypt(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_one_way_cipher_rejects_decrypt() {
+        let expected = CipherError::Other("this algorithm does not support decryption".to_string());
+        assert_eq!(ensure_decryptable(&Checksum).unwrap_err(), expected);
+        assert!(ensure_decryptable(&caesar::Caesar::new(3)).is_ok());
+
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let result = decrypt_lines(&Checksum, "2a\n3b", true, &mut out, &mut err);
+        assert_eq!(result.unwrap_err(), expected);
+        assert!(out.is_empty());
+
+        assert_eq!(
+            verify_roundtrip(&Checksum, "hi", &Checksum.encrypt("hi").unwrap()).unwrap_err(),
+            expected
+        );
+
+        // 组合中只要有一层是单向变换，整体就不能解密
+        let pipeline = ciphery::pipeline::Pipeline::new()
+            .then(Box::new(caesar::Caesar::new(3)))
+            .unwrap()
+            .then(Box::new(Checksum))
+            .unwrap();
+        assert!(!pipeline.supports_decrypt());
+        assert_eq!(ensure_decryptable(&pipeline).unwrap_err(), expected);
+    }
+
+    #[test]
+    fn test_unarmor_uses_algorithm_from_header() {
+        let run = RunOptions {
+            armor: true,
+            ..RunOptions::default()
+        };
+        let key = Some("3".to_string());
+        let outcome = execute_encrypt(
+            Algorithm::RailFence,
+            "WEAREDISCOVERED",
+            &key,
+            &CipherOptions::default(),
+            &run,
+        )
+        .unwrap();
+        assert!(outcome.output.contains("algo: rail-fence\n"));
+
+        let (algorithm, body) = unarmor(Algorithm::Caesar, outcome.output).unwrap();
+        assert_eq!(algorithm, Algorithm::RailFence);
+        let decrypted = execute_decrypt(algorithm, &body, &key, &CipherOptions::default());
+        assert_eq!(decrypted.unwrap().output, "WEAREDISCOVERED");
+
+        // 非装甲文本原样返回；头部中的未知算法报错
+        assert_eq!(
+            unarmor(Algorithm::Xor, "0a1b".to_string()).unwrap(),
+            (Algorithm::Xor, "0a1b".to_string())
+        );
+        let unknown = armor::armor("enigma", "XYZ");
+        assert!(matches!(
+            unarmor(Algorithm::Caesar, unknown),
+            Err(CipherError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_read_multiline_stops_at_sentinel() {
+        let input = io::Cursor::new("first line\nsecond line\n.\nnot read\n");
+        assert_eq!(read_multiline(input).unwrap(), "first line\nsecond line");
+    }
+
+    #[test]
+    fn test_read_key_line_returns_entered_key() {
+        let input = io::Cursor::new("s3cret key\r\nnot read\n");
+        assert_eq!(read_key_line(input).unwrap(), "s3cret key");
+        // 没有换行符（例如 `printf` 管道）时原样返回
+        assert_eq!(read_key_line(io::Cursor::new("LEMON")).unwrap(), "LEMON");
+        assert_eq!(read_key_line(io::Cursor::new("")).unwrap(), "");
+    }
+
+    #[test]
+    fn test_read_multiline_until_eof() {
+        let input = io::Cursor::new("only line\n  .  \nlast");
+        assert_eq!(read_multiline(input).unwrap(), "only line\n  .  \nlast");
+    }
+
+    #[test]
+    fn test_handlers_report_missing_key() {
+        let text = Some("hello".to_string());
+        for algo in [
+            Algorithm::Caesar,
+            Algorithm::Vigenere,
+            Algorithm::Xor,
+            Algorithm::Columnar,
+        ] {
+            let err = handle_encrypt(
+                &text,
+                &algo,
+                &None,
+                &[],
+                &RunOptions::default(),
+                &CipherOptions::default(),
+            )
+            .unwrap_err();
+            assert!(matches!(err, CipherError::InvalidKey(_)), "{:?}", algo);
+        }
+    }
+
+    #[test]
+    fn test_build_columnar_from_options() {
+        let key = Some("ZEBRAS".to_string());
+        let text = "WEAREDISCOVEREDFLEEATONCE";
+        let options = CipherOptions {
+            columns: Some(columnar::ReadOrder::LeftToRight),
+            ..CipherOptions::default()
+        };
+        let cipher = build_cipher(Algorithm::Columnar, &key, &options).unwrap();
+        assert_eq!(cipher.encrypt(text).unwrap(), "WIREEESEAACDTROFOEVLNDEEC");
+
+        let options = CipherOptions {
+            columns: Some(columnar::ReadOrder::LeftToRight),
+            fill: Some(columnar::WriteOrder::ColumnMajor),
+            ..CipherOptions::default()
+        };
+        assert!(matches!(
+            build_cipher(Algorithm::Columnar, &key, &options),
+            Err(CipherError::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_cipher_option_uses_registry() {
+        // 运行时注册的算法没有对应的 `--algo` 取值，只能通过 `--cipher` 按名称选择
+        ciphery::registry::register("handler-test-words", |_| {
+            Ok(Box::new(reverse::Reverse::words()))
+        });
+        let options = CipherOptions {
+            cipher: Some("handler-test-words".to_string()),
+            ..CipherOptions::default()
+        };
+        // 默认的 `--algo caesar` 被忽略，也不会因为缺少 Caesar 密钥而报错
+        validate_key(&None, Algorithm::Caesar, &options, Some("a b c")).unwrap();
+        let outcome = execute_encrypt(
+            Algorithm::Caesar,
+            "a b c",
+            &None,
+            &options,
+            &RunOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(outcome.output, "c b a");
+        assert!(outcome.explanation.is_none());
+
+        let options = CipherOptions {
+            cipher: Some("no-such-cipher".to_string()),
+            ..CipherOptions::default()
+        };
+        assert_eq!(
+            build_cipher(Algorithm::Caesar, &None, &options).err(),
+            Some(CipherError::InvalidInput(
+                "Algorithm 'no-such-cipher' is not supported".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_unimplemented_algorithm_is_an_error() {
+        let err = handle_encrypt(
+            &Some("hello".to_string()),
+            &Algorithm::Base64,
+            &None,
+            &[],
+            &RunOptions::default(),
+            &CipherOptions::default(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            CipherError::Other("algorithm Base64 not implemented".to_string())
+        );
+    }
+
+    #[test]
+    fn test_checksum_is_rejected_for_other_algorithms() {
+        let options = CipherOptions {
+            checksum: true,
+            ..CipherOptions::default()
+        };
+        let key = Some("LEMON".to_string());
+        assert_eq!(
+            build_cipher(Algorithm::Vigenere, &key, &options).err(),
+            Some(CipherError::InvalidInput(
+                "--checksum is only supported for XOR".to_string()
+            ))
+        );
+        let cipher = build_cipher(Algorithm::Xor, &key, &options).unwrap();
+        assert_eq!(
+            cipher.decrypt(&cipher.encrypt("hi").unwrap()).unwrap(),
+            "hi"
+        );
+
+        // 其他 XOR 专用选项同样不能静默忽略
+        let timing_safe = CipherOptions {
+            timing_safe: Some(16),
+            ..CipherOptions::default()
+        };
+        let encoding = CipherOptions {
+            input_encoding: Some(InputEncoding::Base64),
+            ..CipherOptions::default()
+        };
+        for (options, flag) in [
+            (timing_safe, "--timing-safe"),
+            (encoding, "--input-encoding"),
+        ] {
+            assert_eq!(
+                build_cipher(Algorithm::Caesar, &Some("3".to_string()), &options).err(),
+                Some(CipherError::InvalidInput(format!(
+                    "{} is only supported for XOR",
+                    flag
+                )))
+            );
+            assert!(build_cipher(Algorithm::Xor, &key, &options).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_caesar_letter_key_matches_numeric_key() {
+        let options = CipherOptions::default();
+        let letter = build_cipher(Algorithm::Caesar, &Some("D".to_string()), &options).unwrap();
+        let number = build_cipher(Algorithm::Caesar, &Some("3".to_string()), &options).unwrap();
+        assert_eq!(letter.encrypt("hello").unwrap(), "khoor");
+        assert_eq!(
+            letter.encrypt("hello").unwrap(),
+            number.encrypt("hello").unwrap()
+        );
+
+        // A=0 约定，不区分大小写
+        assert_eq!(parse_caesar_key(&Some("a".to_string())), Ok(0));
+        assert_eq!(parse_caesar_key(&Some("z".to_string())), Ok(25));
+        assert!(matches!(
+            parse_caesar_key(&Some("DE".to_string())),
+            Err(CipherError::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_handlers_report_bad_key() {
+        let text = Some("hello".to_string());
+        for (algo, key) in [
+            (Algorithm::Caesar, "abc"),
+            (Algorithm::Vigenere, "k3y"),
+            (Algorithm::RailFence, "1"),
+        ] {
+            let err = handle_decrypt(
+                &text,
+                &algo,
+                &Some(key.to_string()),
+                &[],
+                &RunOptions::default(),
+                &CipherOptions::default(),
+                false,
+            )
+            .unwrap_err();
+            assert!(matches!(err, CipherError::InvalidKey(_)), "{:?}", algo);
+        }
+    }
+
+    #[test]
+    fn test_handlers_report_unreadable_file() {
+        let path = "definitely/not/a/real/file.txt".to_string();
+        let err = handle_decrypt(
+            &None,
+            &Algorithm::Caesar,
+            &Some("3".to_string()),
+            &[path],
+            &RunOptions::default(),
+            &CipherOptions::default(),
+            false,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            CipherError::IoError {
+                kind: io::ErrorKind::NotFound,
+                ..
+            }
+        ));
+    }
+
+    #[cfg(not(feature = "clipboard"))]
+    #[test]
+    fn test_copy_without_clipboard_feature_is_noop() {
+        assert!(!copy_to_clipboard("secret"));
+    }
 }
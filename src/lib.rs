@@ -3,9 +3,29 @@
 //! 该库提供了多种加密算法的实现，目前包含：
 //! - 凯撒密码 (Caesar Cipher)
 
+pub mod analysis;
+pub mod armor;
+pub mod atbash;
+pub mod beaufort;
 pub mod caesar;
+pub mod columnar;
 pub mod error;
+pub mod feistel;
+pub mod morse;
+pub mod nato;
+#[cfg(feature = "normalize")]
+pub mod normalize;
+#[cfg(feature = "regex")]
+pub mod only_matching;
+pub mod pipeline;
+pub mod playfair;
+pub mod polybius;
+pub mod prelude;
 pub mod rail_fence;
+pub mod registry;
+pub mod reverse;
+pub mod square;
+mod util;
 pub mod vigenere;
 pub mod xor;
 
@@ -26,8 +46,187 @@ pub trait Cipher {
     ///
     /// 成功时返回 `Ok(String)`，失败时返回 `Err(CipherError)`
     fn decrypt(&self, text: &str) -> Result<String, CipherError>;
+
+    /// 返回当前算法的"逆算法"
+    ///
+    /// 逆算法的 `encrypt` 等价于原算法的 `decrypt`，反之亦然。
+    /// 这样在组合多个算法时，只需对每一层取逆即可得到整体的解密流程，
+    /// 而无需分别编写加密/解密两套逻辑。
+    fn inverse(&self) -> Box<dyn Cipher>;
+
+    /// 构造该算法需要什么样的密钥
+    ///
+    /// 供 UI 与 WASM 层在运行时决定渲染哪种输入控件（数字框、文本框……）。
+    fn key_requirement(&self) -> KeyRequirement;
+
+    /// 输出是否为"二进制安全"的编码文本（如 hex / base64）
+    ///
+    /// 这类输出虽然是可打印文本，但并不保留字母结构，
+    /// 不应再交给只处理字母的算法（如 Caesar、Vigenere）继续加密。
+    /// 组合算法（[`pipeline::Pipeline`]）据此拒绝不合法的组合。
+    /// 默认返回 `false`，即输出仍是"字母保留"的文本。
+    fn output_is_binary_safe(&self) -> bool {
+        false
+    }
+
+    /// 是否为"自反"(reciprocal) 算法，即加密与解密是同一个操作：`encrypt(encrypt(x)) == x`
+    ///
+    /// UI 可以据此对这类算法隐藏"加密/解密"的区分。默认返回 `false`。
+    fn is_reciprocal(&self) -> bool {
+        false
+    }
+
+    /// 是否支持解密
+    ///
+    /// 单向变换（如类似哈希的混淆）没有逆运算，应返回 `false`，
+    /// UI 据此隐藏或禁用解密入口，CLI 则直接拒绝解密请求。默认返回 `true`。
+    fn supports_decrypt(&self) -> bool {
+        true
+    }
+
+    /// 使用新的密钥构造一个同类算法，其余设置（严格模式、编码、合并字母对等）保持不变
+    ///
+    /// 便于批量工具复用同一套配置轮换密钥，而无需从头重新构造。
+    /// 新密钥的格式与对应构造函数相同，不合法时返回 `CipherError::InvalidKey`。
+    /// 默认实现用于无密钥的算法（如 Atbash、Morse）与组合算法，直接返回 `CipherError::InvalidKey`。
+    fn rekey(&self, _new_key: &str) -> Result<Box<dyn Cipher>, CipherError> {
+        Err(CipherError::InvalidKey(
+            "this cipher does not take a key".to_string(),
+        ))
+    }
 }
 
+/// 算法所需的密钥类型，见 [`Cipher::key_requirement`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyRequirement {
+    /// 不需要密钥（如 ROT13、Atbash、Morse、NATO）
+    None,
+    /// 数字偏移量（如 Caesar）
+    NumericShift,
+    /// 字母关键词（如 Vigenere、Beaufort）
+    Keyword,
+    /// 栅栏层数（Rail Fence），必须 >= 2
+    RailCount,
+    /// 任意字节串（如 XOR）
+    Bytes,
+}
+
+impl KeyRequirement {
+    /// 稳定的字符串名称，便于跨语言（如 JS）使用
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyRequirement::None => "none",
+            KeyRequirement::NumericShift => "numeric_shift",
+            KeyRequirement::Keyword => "keyword",
+            KeyRequirement::RailCount => "rail_count",
+            KeyRequirement::Bytes => "bytes",
+        }
+    }
+}
+
+/// 通用的"逆算法"包装器
+///
+/// 将内部算法的 `encrypt` 与 `decrypt` 对调，
+/// 适用于那些没有现成"逆密钥"可用的算法（如 Vigenere、Xor、Rail Fence）。
+#[derive(Clone)]
+pub struct Inverse<C>(pub C);
+
+impl<C: Cipher + Clone + 'static> Cipher for Inverse<C> {
+    fn encrypt(&self, text: &str) -> Result<String, CipherError> {
+        self.0.decrypt(text)
+    }
+
+    fn decrypt(&self, text: &str) -> Result<String, CipherError> {
+        self.0.encrypt(text)
+    }
+
+    fn inverse(&self) -> Box<dyn Cipher> {
+        // 逆的逆就是原算法本身
+        Box::new(self.0.clone())
+    }
+
+    fn key_requirement(&self) -> KeyRequirement {
+        self.0.key_requirement()
+    }
+
+    fn is_reciprocal(&self) -> bool {
+        self.0.is_reciprocal()
+    }
+
+    fn rekey(&self, new_key: &str) -> Result<Box<dyn Cipher>, CipherError> {
+        // 换密钥后再取逆，保持"逆算法"的语义
+        Ok(self.0.rekey(new_key)?.inverse())
+    }
+}
+
+/// 在 `Debug` 输出中代替密钥的占位符，只暴露密钥长度而不暴露内容
+///
+/// 派生的 `Debug` 会把密钥原样写进日志与 panic 信息，因此各个带密钥的算法都手动实现 `Debug`，
+/// 用它替换密钥字段，便于调试又不泄露秘密。新增带密钥的算法时也应遵循这一约定。
+pub(crate) struct Redacted {
+    /// 密钥长度；像 Caesar 偏移量这种长度无意义的密钥为 `None`
+    pub(crate) len: Option<usize>,
+}
+
+impl std::fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.len {
+            Some(len) => write!(f, "<redacted, len={}>", len),
+            None => write!(f, "<redacted>"),
+        }
+    }
+}
+
+/// 严格模式下的输入检查：替换类算法遇到无法变换的字符时报错，而非原样保留
+///
+/// 返回的 `CipherError::InvalidInput` 中包含第一个非 ASCII 字母字符及其字符位置（从 0 开始）。
+pub(crate) fn ensure_alphabetic(text: &str) -> Result<(), CipherError> {
+    match text.chars().enumerate().find(|(_, c)| !c.is_ascii_alphabetic()) {
+        Some((position, c)) => Err(CipherError::InvalidInput(format!(
+            "non-alphabetic character '{}' at position {} (strict mode)",
+            c, position
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// 文件名安全模式下的输入检查：只允许 `[A-Za-z0-9]` 以及 `.`、`_`、`-`
+///
+/// 这些字符变换后仍落在同一字符集内，从而保证输出依然是合法的文件名；
+/// 遇到其他字符（空格、`/`、非 ASCII 字符等）时返回 `CipherError::InvalidInput`，包含该字符及其字符位置。
+pub(crate) fn ensure_safe_charset(text: &str) -> Result<(), CipherError> {
+    match text
+        .chars()
+        .enumerate()
+        .find(|(_, c)| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')))
+    {
+        Some((position, c)) => Err(CipherError::InvalidInput(format!(
+            "character '{}' at position {} is not allowed in safe-charset mode (only [A-Za-z0-9._-])",
+            c, position
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// 根据算法名与密钥字符串构造加密引擎
+///
+/// 算法名与 WASM 接口保持一致：`caesar`、`rot13`、`atbash`、`morse`、`nato`、`polybius`、`vigenere`、`beaufort`、`playfair`、`columnar`、`xor`、`feistel`、`rail_fence`、`reverse`、`reverse_words`。
+/// Feistel 的密钥写作 `密钥[:轮数]`，省略轮数时为 [`feistel::DEFAULT_ROUNDS`]。
+/// 数字密钥（Caesar 的偏移量、Rail Fence 的层数）解析失败时返回 `CipherError::InvalidKey`，
+/// 不支持的算法名返回 `CipherError::InvalidInput`。
+///
+/// 算法通过全局 [`registry`] 按名称查找，经 [`registry::register`] 注册的自定义算法同样可以在这里构造。
+///
+/// # 示例
+///
+/// ```
+/// let cipher = ciphery::build_cipher("caesar", "3").unwrap();
+/// assert_eq!(cipher.encrypt("hello").unwrap(), "khoor");
+/// ```
+pub fn build_cipher(algo: &str, key: &str) -> Result<Box<dyn Cipher>, CipherError> {
+    // 空字符串视为"没有提供密钥"
+    registry::build(algo, (!key.is_empty()).then_some(key))
+}
 
 // ==========================================
 // WebAssembly (WASM) 暴露接口
@@ -40,50 +239,10 @@ use wasm_bindgen::prelude::*;
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
 pub fn wasm_encrypt(algo: &str, text: &str, key: &str) -> String {
-    match algo {
-        "caesar" => {
-            // 解析密钥
-            let shift: u8 = key.parse().unwrap_or(0) % 26;
-            let cipher = crate::caesar::Caesar::new(shift);
-            
-            // 调用你原有的 encrypt 方法（根据你的代码结构，这里假设返回 Result<String, _>）
-            match cipher.encrypt(text) {
-                Ok(res) => res,
-                Err(e) => format!("Error: {}", e),
-            }
-        },
-        "rot13" => {
-            let cipher = crate::caesar::Caesar::new(13);
-            match cipher.encrypt(text) {
-                Ok(res) => res,
-                Err(e) => format!("Error: {}", e),
-            }
-        },
-        "vigenere" => {
-            let cipher = crate::vigenere::Vigenere::new(key);
-            match cipher.encrypt(text) {
-                Ok(res) => res,
-                Err(e) => format!("Error: {}", e),
-            }
-        },
-        "xor"  => {
-            let cipher = crate::xor::Xor::new(key);
-            match cipher.encrypt(text) {
-                Ok(res) => res,
-                Err(e) => format!("Error: {}", e),
-            }
-        },
-        "rail_fence" => {
-            let rails: usize = key.parse().unwrap_or(3);
-            match crate::rail_fence::RailFence::new(rails) {
-                Ok(cipher) => match cipher.encrypt(text) {
-                    Ok(res) => res,
-                    Err(e) => format!("Error: {}", e),
-                },
-                Err(e) => format!("Error: {}", e),
-            }
-        }
-        _ => format!("Algorithm '{}' not supported yet in Web", algo),
+    // 与 WasmCipher 一样经由注册表构造，运行时注册的算法同样可用，密钥错误也会如实报告
+    match build_cipher(algo, key).and_then(|cipher| cipher.encrypt(text)) {
+        Ok(res) => res,
+        Err(e) => format!("Error: {}", e),
     }
 }
 
@@ -91,48 +250,146 @@ pub fn wasm_encrypt(algo: &str, text: &str, key: &str) -> String {
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
 pub fn wasm_decrypt(algo: &str, text: &str, key: &str) -> String {
-    match algo {
-        "caesar" => {
-            let shift: u8 = key.parse().unwrap_or(0) % 26;
-            let cipher = crate::caesar::Caesar::new(shift);
-            
-            match cipher.decrypt(text) {
-                Ok(res) => res,
-                Err(e) => format!("Error: {}", e),
-            }
-        },
-        "rot13" => {
-            let cipher = crate::caesar::Caesar::new(13);
-            match cipher.decrypt(text) {
-                Ok(res) => res,
-                Err(e) => format!("Error: {}", e),
-            }
-        },
-        "vigenere" => {
-            let cipher = crate::vigenere::Vigenere::new(key);
-            match cipher.decrypt(text) {
-                Ok(res) => res,
-                Err(e) => format!("Error: {}", e),
-            }
-        },
-        "xor"  => {
-            let cipher = crate::xor::Xor::new(key);
-            match cipher.decrypt(text) {
-                Ok(res) => res,
-                Err(e) => format!("Error: {}", e),
-            }
-        },
-        "rail_fence" => {
-            // 解析密钥为栅栏层数
-            let rails: usize = key.parse().unwrap_or(3);
-            match crate::rail_fence::RailFence::new(rails) {
-                Ok(cipher) => match cipher.decrypt(text) {
-                    Ok(res) => res,
-                    Err(e) => format!("Error: {}", e),
-                },
-                Err(e) => format!("Error: {}", e),
+    match build_cipher(algo, key).and_then(|cipher| cipher.decrypt(text)) {
+        Ok(res) => res,
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+/// 可复用的 WASM 加密句柄
+///
+/// 由 JS 侧以 `(algo, key)` 构造一次，之后可多次调用 `encrypt` / `decrypt`，
+/// 避免每次调用都重新解析密钥、重新创建加密引擎。
+#[cfg(target_arch = "wasm32")]
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub struct WasmCipher {
+    inner: Box<dyn Cipher>,
+}
+
+#[cfg(target_arch = "wasm32")]
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl WasmCipher {
+    #[wasm_bindgen(constructor)]
+    pub fn new(algo: &str, key: &str) -> Result<WasmCipher, JsValue> {
+        build_cipher(algo, key)
+            .map(|inner| WasmCipher { inner })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    pub fn encrypt(&self, text: &str) -> Result<String, JsValue> {
+        self.inner
+            .encrypt(text)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    pub fn decrypt(&self, text: &str) -> Result<String, JsValue> {
+        self.inner
+            .decrypt(text)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// 密钥类型名称（`none` / `numeric_shift` / `keyword` / `rail_count` / `bytes`）
+    #[wasm_bindgen(js_name = keyRequirement)]
+    pub fn key_requirement(&self) -> String {
+        self.inner.key_requirement().as_str().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_cipher_reused_for_many_calls() {
+        // 与 WasmCipher 相同：只构造一次，之后多次加解密
+        let cipher = build_cipher("vigenere", "LEMON").unwrap();
+        assert_eq!(cipher.encrypt("ATTACK AT DAWN").unwrap(), "LXFOPV EF RNHR");
+        assert_eq!(cipher.encrypt("hello").unwrap(), "sixzb");
+        assert_eq!(cipher.decrypt("sixzb").unwrap(), "hello");
+    }
+
+    #[test]
+    fn test_key_requirement_per_cipher() {
+        let expected = [
+            ("caesar", "3", KeyRequirement::NumericShift),
+            ("rot13", "", KeyRequirement::None),
+            ("atbash", "", KeyRequirement::None),
+            ("morse", "", KeyRequirement::None),
+            ("nato", "", KeyRequirement::None),
+            ("polybius", "", KeyRequirement::None),
+            ("reverse", "", KeyRequirement::None),
+            ("playfair", "KEY", KeyRequirement::Keyword),
+            ("vigenere", "KEY", KeyRequirement::Keyword),
+            ("beaufort", "KEY", KeyRequirement::Keyword),
+            ("columnar", "KEY", KeyRequirement::Keyword),
+            ("xor", "key", KeyRequirement::Bytes),
+            ("rail_fence", "3", KeyRequirement::RailCount),
+        ];
+        for (algo, key, requirement) in expected {
+            let cipher = build_cipher(algo, key).unwrap();
+            assert_eq!(cipher.key_requirement(), requirement, "{}", algo);
+            // 逆算法需要的密钥与原算法相同
+            assert_eq!(cipher.inverse().key_requirement(), requirement, "{}", algo);
+        }
+        assert_eq!(
+            pipeline::Pipeline::new().key_requirement(),
+            KeyRequirement::None
+        );
+    }
+
+    #[test]
+    fn test_is_reciprocal_per_cipher() {
+        let expected = [
+            ("caesar", "3", false),
+            ("caesar", "13", true),
+            ("rot13", "", true),
+            ("atbash", "", true),
+            ("beaufort", "KEY", true),
+            ("vigenere", "KEY", false),
+            ("xor", "key", false),
+            ("morse", "", false),
+            ("reverse", "", true),
+            ("reverse_words", "", true),
+            ("rail_fence", "3", false),
+        ];
+        let text = "Attack at dawn";
+        for (algo, key, reciprocal) in expected {
+            let cipher = build_cipher(algo, key).unwrap();
+            assert_eq!(cipher.is_reciprocal(), reciprocal, "{}", algo);
+            if reciprocal {
+                let twice = cipher.encrypt(&cipher.encrypt(text).unwrap()).unwrap();
+                assert_eq!(twice, text, "{}", algo);
             }
-        },
-        _ => format!("Algorithm '{}' not supported yet in Web", algo),
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_rekey_keyless_and_inverse() {
+        assert!(matches!(
+            atbash::Atbash::new().rekey("KEY"),
+            Err(CipherError::InvalidKey(_))
+        ));
+
+        // 逆算法换密钥后依然是逆算法
+        let inverse = Inverse(caesar::Caesar::new(3)).rekey("5").unwrap();
+        assert_eq!(inverse.encrypt("mjqqt").unwrap(), "hello");
+    }
+
+    #[test]
+    fn test_build_cipher_errors() {
+        assert!(matches!(
+            build_cipher("caesar", "abc"),
+            Err(CipherError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            build_cipher("rail_fence", "1"),
+            Err(CipherError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            build_cipher("enigma", ""),
+            Err(CipherError::InvalidInput(_))
+        ));
+    }
+}
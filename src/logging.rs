@@ -0,0 +1,60 @@
+//! 诊断信息输出模块
+//!
+//! 提供 `info!`、`warning!`、`error!` 三个宏，用于输出 `[info]` / `[warning]` / `[error]` 这类诊断信息：
+//! - 启用 `log` feature 时转发给 `log` crate 的 `info!` / `warn!` / `error!`，由 [`init`] 初始化的 `env_logger` 输出到 stderr，
+//!   可以通过 `RUST_LOG` 环境变量控制级别（默认 `info`），嵌入方也可以换成自己的 logger；
+//! - 未启用时退回到 `println!`，输出与以前完全一致。
+//!
+//! 加解密结果本身（`[result]`、`[summary]` 等）始终通过 `println!` 输出到 stdout，不经过这里。
+
+#[cfg(feature = "log")]
+macro_rules! info {
+    ($($arg:tt)*) => { log::info!($($arg)*) };
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! info {
+    ($($arg:tt)*) => { println!("[info] {}", format_args!($($arg)*)) };
+}
+
+#[cfg(feature = "log")]
+macro_rules! warning {
+    ($($arg:tt)*) => { log::warn!($($arg)*) };
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! warning {
+    ($($arg:tt)*) => { println!("[warning] {}", format_args!($($arg)*)) };
+}
+
+#[cfg(feature = "log")]
+macro_rules! error {
+    ($($arg:tt)*) => { log::error!($($arg)*) };
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! error {
+    ($($arg:tt)*) => { println!("[error] {}", format_args!($($arg)*)) };
+}
+
+pub(crate) use {error, info, warning};
+
+/// 初始化日志输出：默认级别为 `info`，可用 `RUST_LOG` 覆盖；输出格式与未启用 `log` feature 时的前缀保持一致
+#[cfg(feature = "log")]
+pub fn init() {
+    use std::io::Write;
+
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
+        .format(|buf, record| {
+            let prefix = match record.level() {
+                log::Level::Warn => "warning".to_string(),
+                level => level.as_str().to_lowercase(),
+            };
+            writeln!(buf, "[{}] {}", prefix, record.args())
+        })
+        .init();
+}
+
+/// 未启用 `log` feature 时无需初始化
+#[cfg(not(feature = "log"))]
+pub fn init() {}
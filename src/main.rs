@@ -1,10 +1,13 @@
 ﻿mod cli;
 mod handler;
+mod logging;
+mod progress;
 
 use clap::Parser;
 use cli::Cli;
 
 fn main() {
     let cli = Cli::parse();
+    logging::init();
     handler::run(cli.command.as_ref());
 }
@@ -0,0 +1,239 @@
+//! 摩尔斯电码 (Morse Code) 的实现
+//!
+//! 严格来说摩尔斯电码是一种编码而非加密，但它同样满足"可逆变换"的抽象，
+//! 因此也实现了 `Cipher` Trait。编码时字母之间以一个空格分隔，单词之间以 `/` 分隔。
+
+use crate::{Cipher, CipherError, Inverse, KeyRequirement};
+
+/// 字符与摩尔斯电码的对照表（ITU 标准）
+const TABLE: [(char, &str); 54] = [
+    ('A', ".-"),
+    ('B', "-..."),
+    ('C', "-.-."),
+    ('D', "-.."),
+    ('E', "."),
+    ('F', "..-."),
+    ('G', "--."),
+    ('H', "...."),
+    ('I', ".."),
+    ('J', ".---"),
+    ('K', "-.-"),
+    ('L', ".-.."),
+    ('M', "--"),
+    ('N', "-."),
+    ('O', "---"),
+    ('P', ".--."),
+    ('Q', "--.-"),
+    ('R', ".-."),
+    ('S', "..."),
+    ('T', "-"),
+    ('U', "..-"),
+    ('V', "...-"),
+    ('W', ".--"),
+    ('X', "-..-"),
+    ('Y', "-.--"),
+    ('Z', "--.."),
+    ('0', "-----"),
+    ('1', ".----"),
+    ('2', "..---"),
+    ('3', "...--"),
+    ('4', "....-"),
+    ('5', "....."),
+    ('6', "-...."),
+    ('7', "--..."),
+    ('8', "---.."),
+    ('9', "----."),
+    ('.', ".-.-.-"),
+    (',', "--..--"),
+    ('?', "..--.."),
+    ('\'', ".----."),
+    ('!', "-.-.--"),
+    ('/', "-..-."),
+    ('(', "-.--."),
+    (')', "-.--.-"),
+    ('&', ".-..."),
+    (':', "---..."),
+    (';', "-.-.-."),
+    ('=', "-...-"),
+    ('+', ".-.-."),
+    ('-', "-....-"),
+    ('_', "..--.-"),
+    ('"', ".-..-."),
+    ('$', "...-..-"),
+    ('@', ".--.-."),
+];
+
+/// 单词分隔符
+const WORD_SEPARATOR: &str = "/";
+
+/// 摩尔斯电码（无密钥）
+#[derive(Debug, Clone, Default)]
+pub struct Morse;
+
+impl Morse {
+    /// 创建一个新的摩尔斯电码实例
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn encode_char(c: char) -> Option<&'static str> {
+    let upper = c.to_ascii_uppercase();
+    TABLE
+        .iter()
+        .find(|(plain, _)| *plain == upper)
+        .map(|(_, code)| *code)
+}
+
+fn decode_token(token: &str) -> Option<char> {
+    TABLE
+        .iter()
+        .find(|(_, code)| *code == token)
+        .map(|(plain, _)| *plain)
+}
+
+impl Cipher for Morse {
+    /// 编码：字母不区分大小写，空格视为单词分隔；无法编码的字符返回 `CipherError::InvalidToken`
+    fn encrypt(&self, text: &str) -> Result<String, CipherError> {
+        let mut tokens = Vec::new();
+        for (position, c) in text.chars().enumerate() {
+            if c == ' ' {
+                tokens.push(WORD_SEPARATOR);
+                continue;
+            }
+            let code = encode_char(c).ok_or_else(|| CipherError::InvalidToken {
+                token: c.to_string(),
+                position,
+            })?;
+            tokens.push(code);
+        }
+        Ok(tokens.join(" "))
+    }
+
+    /// 解码：任意长度的空白（空格、制表符、换行）都视为符号之间的分隔，
+    /// `/` 表示单词分隔（两侧的空白可有可无）；首尾空白会被忽略。
+    /// 无法识别的符号返回 `CipherError::InvalidToken`，其中 `position` 为该符号在输入中的起始字符位置（从 0 开始）
+    fn decrypt(&self, text: &str) -> Result<String, CipherError> {
+        let mut plain = String::new();
+        let mut token = String::new();
+        let mut token_start = 0;
+        // 在末尾补一个空白，保证最后一个符号也会被处理
+        for (position, c) in text.chars().chain([' ']).enumerate() {
+            if c.is_whitespace() || WORD_SEPARATOR.contains(c) {
+                if !token.is_empty() {
+                    let decoded =
+                        decode_token(&token).ok_or_else(|| CipherError::InvalidToken {
+                            token: token.clone(),
+                            position: token_start,
+                        })?;
+                    plain.push(decoded);
+                    token.clear();
+                }
+                if WORD_SEPARATOR.contains(c) {
+                    plain.push(' ');
+                }
+            } else {
+                if token.is_empty() {
+                    token_start = position;
+                }
+                token.push(c);
+            }
+        }
+        Ok(plain)
+    }
+
+    fn inverse(&self) -> Box<dyn Cipher> {
+        Box::new(Inverse(self.clone()))
+    }
+
+    fn key_requirement(&self) -> KeyRequirement {
+        KeyRequirement::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_morse_encode() {
+        let cipher = Morse::new();
+        assert_eq!(cipher.encrypt("SOS").unwrap(), "... --- ...");
+        assert_eq!(cipher.encrypt("Hi 5").unwrap(), ".... .. / .....");
+    }
+
+    #[test]
+    fn test_morse_roundtrip() {
+        let cipher = Morse::new();
+        let encoded = cipher.encrypt("Hello, World!").unwrap();
+        assert_eq!(cipher.decrypt(&encoded).unwrap(), "HELLO, WORLD!");
+    }
+
+    #[test]
+    fn test_morse_reports_bad_token_position() {
+        // 第三个符号 "......" 不存在，起始位置为 "... --- " 之后的第 8 个字符
+        let result = Morse::new().decrypt("... --- ...... ...");
+        let err = result.unwrap_err();
+        assert_eq!(
+            err,
+            CipherError::InvalidToken {
+                token: "......".to_string(),
+                position: 8,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "无效的输入: invalid token '......' at position 8"
+        );
+    }
+
+    #[test]
+    fn test_morse_decode_tolerates_inconsistent_spacing() {
+        let cipher = Morse::new();
+        for input in [
+            "... --- ...",
+            "...   ---    ...",
+            "...\t---\t...",
+            "...\n---\r\n...",
+            "  ... --- ...  \n",
+        ] {
+            assert_eq!(cipher.decrypt(input).unwrap(), "SOS", "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_morse_decode_word_separator_spacing() {
+        let cipher = Morse::new();
+        for input in [
+            "... --- ... / ... --- ...",
+            "... --- .../... --- ...",
+            "... --- ...   /\t... --- ...",
+        ] {
+            assert_eq!(cipher.decrypt(input).unwrap(), "SOS SOS", "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_morse_decode_still_rejects_malformed_symbols() {
+        let result = Morse::new().decrypt("...  -x-\t...");
+        assert_eq!(
+            result,
+            Err(CipherError::InvalidToken {
+                token: "-x-".to_string(),
+                position: 5,
+            })
+        );
+    }
+
+    #[test]
+    fn test_morse_encode_reports_unknown_char() {
+        let result = Morse::new().encrypt("ab世");
+        assert_eq!(
+            result,
+            Err(CipherError::InvalidToken {
+                token: "世".to_string(),
+                position: 2,
+            })
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! 北约音标字母 (NATO Phonetic Alphabet) 的实现
+//!
+//! 与摩尔斯电码一样，这是一种编码而非加密，常用于需要"念出来"的场景（电话、无线电）。
+//! 编码时每个字母/数字对应一个单词，以空格分隔，单词之间以 `/` 分隔。
+
+use crate::{Cipher, CipherError, Inverse, KeyRequirement};
+
+/// 字符与音标单词的对照表
+const TABLE: [(char, &str); 36] = [
+    ('A', "Alpha"),
+    ('B', "Bravo"),
+    ('C', "Charlie"),
+    ('D', "Delta"),
+    ('E', "Echo"),
+    ('F', "Foxtrot"),
+    ('G', "Golf"),
+    ('H', "Hotel"),
+    ('I', "India"),
+    ('J', "Juliett"),
+    ('K', "Kilo"),
+    ('L', "Lima"),
+    ('M', "Mike"),
+    ('N', "November"),
+    ('O', "Oscar"),
+    ('P', "Papa"),
+    ('Q', "Quebec"),
+    ('R', "Romeo"),
+    ('S', "Sierra"),
+    ('T', "Tango"),
+    ('U', "Uniform"),
+    ('V', "Victor"),
+    ('W', "Whiskey"),
+    ('X', "X-ray"),
+    ('Y', "Yankee"),
+    ('Z', "Zulu"),
+    ('0', "Zero"),
+    ('1', "One"),
+    ('2', "Two"),
+    ('3', "Three"),
+    ('4', "Four"),
+    ('5', "Five"),
+    ('6', "Six"),
+    ('7', "Seven"),
+    ('8', "Eight"),
+    ('9', "Nine"),
+];
+
+/// 单词分隔符
+const WORD_SEPARATOR: &str = "/";
+
+/// 北约音标字母（无密钥）
+#[derive(Debug, Clone, Default)]
+pub struct Nato;
+
+impl Nato {
+    /// 创建一个新的北约音标字母实例
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn encode_char(c: char) -> Option<&'static str> {
+    let upper = c.to_ascii_uppercase();
+    TABLE
+        .iter()
+        .find(|(plain, _)| *plain == upper)
+        .map(|(_, word)| *word)
+}
+
+fn decode_word(word: &str) -> Option<char> {
+    // 念读时大小写无关紧要，"alpha"、"ALPHA" 都能识别
+    TABLE
+        .iter()
+        .find(|(_, code)| code.eq_ignore_ascii_case(word))
+        .map(|(plain, _)| *plain)
+}
+
+impl Cipher for Nato {
+    /// 编码：字母不区分大小写，空格视为单词分隔；无法编码的字符返回 `CipherError::InvalidToken`
+    fn encrypt(&self, text: &str) -> Result<String, CipherError> {
+        let mut words = Vec::new();
+        for (position, c) in text.chars().enumerate() {
+            if c == ' ' {
+                words.push(WORD_SEPARATOR);
+                continue;
+            }
+            let word = encode_char(c).ok_or_else(|| CipherError::InvalidToken {
+                token: c.to_string(),
+                position,
+            })?;
+            words.push(word);
+        }
+        Ok(words.join(" "))
+    }
+
+    /// 解码：以空白分隔每个单词，`/` 表示单词分隔；无法识别的单词返回 `CipherError::InvalidInput`
+    fn decrypt(&self, text: &str) -> Result<String, CipherError> {
+        text.split_whitespace()
+            .map(|word| {
+                if word == WORD_SEPARATOR {
+                    Ok(' ')
+                } else {
+                    decode_word(word).ok_or_else(|| {
+                        CipherError::InvalidInput(format!("unknown NATO word '{}'", word))
+                    })
+                }
+            })
+            .collect()
+    }
+
+    fn inverse(&self) -> Box<dyn Cipher> {
+        Box::new(Inverse(self.clone()))
+    }
+
+    fn key_requirement(&self) -> KeyRequirement {
+        KeyRequirement::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nato_encode() {
+        let cipher = Nato::new();
+        assert_eq!(cipher.encrypt("ABC").unwrap(), "Alpha Bravo Charlie");
+        assert_eq!(cipher.encrypt("x 42").unwrap(), "X-ray / Four Two");
+    }
+
+    #[test]
+    fn test_nato_roundtrip() {
+        let cipher = Nato::new();
+        let encoded = cipher.encrypt("Flight 370").unwrap();
+        assert_eq!(cipher.decrypt(&encoded).unwrap(), "FLIGHT 370");
+        assert_eq!(cipher.decrypt("alpha  BRAVO\ncharlie").unwrap(), "ABC");
+    }
+
+    #[test]
+    fn test_nato_unknown_word() {
+        let result = Nato::new().decrypt("Alpha Bravo Banana");
+        assert_eq!(
+            result,
+            Err(CipherError::InvalidInput(
+                "unknown NATO word 'Banana'".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! 在加解密之前做 Unicode NFC 规范化的包装器（需启用 `normalize` feature）
+//!
+//! 同一个字符在 Unicode 中可能有多种写法：例如 `é` 既可以是单个码点 U+00E9（组合形式），
+//! 也可以是 `e` 加上组合重音符 U+0301（分解形式）。两者看起来完全一样，
+//! 但逐字符处理的算法会得到不同的结果。包装后，输入会先统一转换为 NFC 形式，
+//! 使看起来相同的文本总是得到相同的密文。
+//!
+//! 注意：规范化可能改变文本的字节长度（例如 `e` + U+0301 占 3 个字节，`é` 只占 2 个字节），
+//! 因此对 XOR 这类按字节处理的算法，密文长度可能与原始输入的字节数不同；
+//! 解密得到的也是规范化后的文本，而不一定是逐字节相同的原文。
+
+use crate::{Cipher, CipherError, KeyRequirement};
+use unicode_normalization::UnicodeNormalization;
+
+/// 把文本转换为 NFC（标准组合）形式
+pub fn nfc(text: &str) -> String {
+    text.nfc().collect()
+}
+
+/// 先把输入规范化为 NFC，再交给内部算法处理
+pub struct Normalized {
+    inner: Box<dyn Cipher>,
+}
+
+impl Normalized {
+    /// 用 NFC 规范化包装一个已有的算法
+    pub fn new(inner: Box<dyn Cipher>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Cipher for Normalized {
+    fn encrypt(&self, text: &str) -> Result<String, CipherError> {
+        self.inner.encrypt(&nfc(text))
+    }
+
+    fn decrypt(&self, text: &str) -> Result<String, CipherError> {
+        self.inner.decrypt(&nfc(text))
+    }
+
+    fn inverse(&self) -> Box<dyn Cipher> {
+        Box::new(Normalized {
+            inner: self.inner.inverse(),
+        })
+    }
+
+    fn key_requirement(&self) -> KeyRequirement {
+        self.inner.key_requirement()
+    }
+
+    fn output_is_binary_safe(&self) -> bool {
+        self.inner.output_is_binary_safe()
+    }
+
+    fn is_reciprocal(&self) -> bool {
+        self.inner.is_reciprocal()
+    }
+
+    fn supports_decrypt(&self) -> bool {
+        self.inner.supports_decrypt()
+    }
+
+    fn rekey(&self, new_key: &str) -> Result<Box<dyn Cipher>, CipherError> {
+        // 新密钥同样先做规范化
+        Ok(Box::new(Normalized {
+            inner: self.inner.rekey(&nfc(new_key))?,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{caesar::Caesar, xor::Xor};
+
+    const COMPOSED: &str = "Caf\u{e9} cr\u{e8}me";
+    const DECOMPOSED: &str = "Cafe\u{301} cre\u{300}me";
+
+    #[test]
+    fn test_nfc_and_nfd_give_identical_caesar_output() {
+        // 未规范化时，分解形式中的基础字母 `e` 会被平移，两种写法的密文不同
+        let plain = Caesar::new(3);
+        assert_ne!(
+            plain.encrypt(COMPOSED).unwrap(),
+            plain.encrypt(DECOMPOSED).unwrap()
+        );
+
+        let cipher = Normalized::new(Box::new(Caesar::new(3)));
+        let encrypted = cipher.encrypt(COMPOSED).unwrap();
+        assert_eq!(encrypted, "Fdi\u{e9} fu\u{e8}ph");
+        assert_eq!(cipher.encrypt(DECOMPOSED).unwrap(), encrypted);
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), COMPOSED);
+    }
+
+    #[test]
+    fn test_normalization_can_change_xor_byte_length() {
+        let cipher = Normalized::new(Box::new(Xor::new("key").unwrap()));
+        let encrypted = cipher.encrypt(DECOMPOSED).unwrap();
+        // hex 密文的长度对应规范化后（更短）的字节数
+        assert_eq!(encrypted.len(), COMPOSED.len() * 2);
+        assert!(DECOMPOSED.len() > COMPOSED.len());
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), COMPOSED);
+    }
+}
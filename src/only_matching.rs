@@ -0,0 +1,117 @@
+//! 只对正则匹配区域加解密的包装器（需启用 `regex` feature）
+//!
+//! 例如只加密配置文件中 `password=` 之后的值，其余内容原样保留。
+//! 正则中有捕获组时只处理第 1 个捕获组，否则处理整个匹配。
+//!
+//! 注意：解密时会在**密文**上重新执行同一个正则，因此正则必须能在加密结果上匹配到完全相同的区域。
+//! 只有不改变区域"形状"的算法（如对 `[A-Za-z]+` 使用 Caesar、对 `\d+` 使用 Rail Fence）才满足这一点；
+//! 像 XOR 这样把区域变成 hex 的算法，通常需要相应地调整正则。
+
+use crate::{Cipher, CipherError, KeyRequirement};
+use regex::Regex;
+
+/// 只对匹配区域生效的加密算法
+pub struct OnlyMatching {
+    inner: Box<dyn Cipher>,
+    pattern: Regex,
+}
+
+impl OnlyMatching {
+    /// 用正则包装一个已有的算法
+    ///
+    /// # 参数
+    ///
+    /// * `inner` - 实际执行加解密的算法
+    /// * `pattern` - 正则表达式，语法错误时返回 `CipherError::InvalidInput`
+    pub fn new(inner: Box<dyn Cipher>, pattern: &str) -> Result<Self, CipherError> {
+        let pattern = Regex::new(pattern)
+            .map_err(|e| CipherError::InvalidInput(format!("invalid regex: {}", e)))?;
+        Ok(Self { inner, pattern })
+    }
+
+    /// 对每个匹配区域调用 `f`，区域之外的文本原样拼接
+    fn apply(
+        &self,
+        text: &str,
+        f: impl Fn(&dyn Cipher, &str) -> Result<String, CipherError>,
+    ) -> Result<String, CipherError> {
+        let mut output = String::with_capacity(text.len());
+        let mut last = 0;
+        for captures in self.pattern.captures_iter(text) {
+            // 第 0 组（整个匹配）总是存在
+            let region = captures.get(1).or_else(|| captures.get(0)).unwrap();
+            output.push_str(&text[last..region.start()]);
+            output.push_str(&f(self.inner.as_ref(), region.as_str())?);
+            last = region.end();
+        }
+        output.push_str(&text[last..]);
+        Ok(output)
+    }
+}
+
+impl Cipher for OnlyMatching {
+    fn encrypt(&self, text: &str) -> Result<String, CipherError> {
+        self.apply(text, |cipher, region| cipher.encrypt(region))
+    }
+
+    fn decrypt(&self, text: &str) -> Result<String, CipherError> {
+        self.apply(text, |cipher, region| cipher.decrypt(region))
+    }
+
+    fn inverse(&self) -> Box<dyn Cipher> {
+        Box::new(OnlyMatching {
+            inner: self.inner.inverse(),
+            pattern: self.pattern.clone(),
+        })
+    }
+
+    fn key_requirement(&self) -> KeyRequirement {
+        self.inner.key_requirement()
+    }
+
+    fn is_reciprocal(&self) -> bool {
+        self.inner.is_reciprocal()
+    }
+
+    fn supports_decrypt(&self) -> bool {
+        self.inner.supports_decrypt()
+    }
+
+    fn rekey(&self, new_key: &str) -> Result<Box<dyn Cipher>, CipherError> {
+        Ok(Box::new(OnlyMatching {
+            inner: self.inner.rekey(new_key)?,
+            pattern: self.pattern.clone(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{caesar::Caesar, rail_fence::RailFence};
+
+    #[test]
+    fn test_only_digits_are_encrypted() {
+        let cipher = OnlyMatching::new(Box::new(RailFence::new(2).unwrap()), r"\d+").unwrap();
+        let text = "order 123456 shipped to zone 7890";
+        let encrypted = cipher.encrypt(text).unwrap();
+        assert_eq!(encrypted, "order 135246 shipped to zone 7980");
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), text);
+        assert_eq!(cipher.inverse().encrypt(&encrypted).unwrap(), text);
+    }
+
+    #[test]
+    fn test_capture_group_limits_region() {
+        let cipher = OnlyMatching::new(Box::new(Caesar::new(3)), r"password=([a-z]+)").unwrap();
+        let text = "user=alice\npassword=secret\n";
+        let encrypted = cipher.encrypt(text).unwrap();
+        assert_eq!(encrypted, "user=alice\npassword=vhfuhw\n");
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), text);
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        let result = OnlyMatching::new(Box::new(Caesar::new(3)), "(unclosed");
+        assert!(matches!(result, Err(CipherError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,295 @@
+//! 组合加密 (Pipeline) 模块
+//!
+//! 将多个加密算法按顺序串联起来：加密时依次经过每一层，
+//! 解密时借助每一层的 [`Cipher::inverse`] 以相反顺序还原。
+//!
+//! 通过 [`Pipeline::then_named`] 按算法名加入的层会记住自己的配置（[`StageConfig`]），
+//! 启用 `serde` feature 后，整个组合可以用 [`Pipeline::to_json`] 保存为 JSON "配方"，
+//! 再用 [`Pipeline::from_json`] 重新加载：
+//!
+//! ```json
+//! {"stages":[{"algo":"caesar","key":"3"},{"algo":"rail_fence","key":"4"}]}
+//! ```
+
+use crate::{Cipher, CipherError, KeyRequirement};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// 组合中一层的配置：算法名（与 [`crate::build_cipher`] 相同）及其密钥
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StageConfig {
+    pub algo: String,
+    /// 无密钥的算法，或保存时隐去了密钥，均为 `None`
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub key: Option<String>,
+}
+
+/// JSON 配方的顶层结构
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct Recipe {
+    stages: Vec<StageConfig>,
+}
+
+/// 由多个算法串联而成的组合算法
+///
+/// 本身也实现了 `Cipher`，因此可以像普通算法一样使用，甚至嵌套组合。
+#[derive(Default)]
+pub struct Pipeline {
+    stages: Vec<Box<dyn Cipher>>,
+    /// 与 `stages` 一一对应；直接通过 [`Pipeline::then`] 加入的层没有可保存的配置，为 `None`
+    configs: Vec<Option<StageConfig>>,
+}
+
+impl Pipeline {
+    /// 创建一个空的组合算法（不做任何变换）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 按算法名与密钥构造一层并追加到末尾，同时记住它的配置以便保存为配方
+    ///
+    /// 算法名与密钥的规则同 [`crate::build_cipher`]；组合是否合法的检查同 [`Pipeline::then`]。
+    pub fn then_named(self, algo: &str, key: Option<&str>) -> Result<Self, CipherError> {
+        let stage = crate::build_cipher(algo, key.unwrap_or(""))?;
+        let mut pipeline = self.then(stage)?;
+        *pipeline
+            .configs
+            .last_mut()
+            .expect("then always pushes a stage") = Some(StageConfig {
+            algo: algo.to_string(),
+            key: key.map(str::to_string),
+        });
+        Ok(pipeline)
+    }
+
+    /// 由一组配置依次构造各层
+    pub fn from_configs(configs: &[StageConfig]) -> Result<Self, CipherError> {
+        configs.iter().try_fold(Self::new(), |pipeline, config| {
+            pipeline.then_named(&config.algo, config.key.as_deref())
+        })
+    }
+
+    /// 各层的配置；存在通过 [`Pipeline::then`] 直接加入、无法描述的层时返回 `CipherError::InvalidInput`
+    ///
+    /// `include_keys` 为 `false` 时隐去所有密钥（分享配方而不泄露密钥），加载前需要重新填上。
+    pub fn configs(&self, include_keys: bool) -> Result<Vec<StageConfig>, CipherError> {
+        self.configs
+            .iter()
+            .enumerate()
+            .map(|(index, config)| {
+                let config = config.as_ref().ok_or_else(|| {
+                    CipherError::InvalidInput(format!(
+                        "stage {} was added as a cipher object and cannot be described",
+                        index + 1
+                    ))
+                })?;
+                Ok(StageConfig {
+                    algo: config.algo.clone(),
+                    key: config.key.clone().filter(|_| include_keys),
+                })
+            })
+            .collect()
+    }
+
+    /// 从 JSON 配方构造组合算法（需启用 `serde` feature）
+    ///
+    /// JSON 格式错误时返回 `CipherError::InvalidInput`；算法名或密钥不合法时的错误同 [`Pipeline::then_named`]。
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, CipherError> {
+        let recipe: Recipe = serde_json::from_str(json)
+            .map_err(|e| CipherError::InvalidInput(format!("invalid recipe: {}", e)))?;
+        Self::from_configs(&recipe.stages)
+    }
+
+    /// 将组合算法保存为 JSON 配方（需启用 `serde` feature），`include_keys` 的含义同 [`Pipeline::configs`]
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self, include_keys: bool) -> Result<String, CipherError> {
+        let recipe = Recipe {
+            stages: self.configs(include_keys)?,
+        };
+        serde_json::to_string(&recipe).map_err(|e| CipherError::Other(e.to_string()))
+    }
+
+    /// 在末尾追加一层算法
+    ///
+    /// 如果上一层输出的是 hex / base64 这类二进制安全的编码文本，
+    /// 而新的一层是只处理字母的算法，则返回 `CipherError::InvalidInput`：
+    /// 字母算法会破坏编码的字符集，得到的中间结果不再是合法的 hex / base64。
+    pub fn then(mut self, stage: Box<dyn Cipher>) -> Result<Self, CipherError> {
+        if let Some(last) = self.stages.last()
+            && last.output_is_binary_safe()
+            && !stage.output_is_binary_safe()
+        {
+            return Err(CipherError::InvalidInput(format!(
+                "stage {} outputs encoded (hex/base64) text and cannot be followed by a letter-based cipher",
+                self.stages.len()
+            )));
+        }
+
+        self.stages.push(stage);
+        self.configs.push(None);
+        Ok(self)
+    }
+
+    /// 组合中的层数
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// 组合是否为空
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+}
+
+impl Cipher for Pipeline {
+    fn encrypt(&self, text: &str) -> Result<String, CipherError> {
+        self.stages
+            .iter()
+            .try_fold(text.to_string(), |acc, stage| stage.encrypt(&acc))
+    }
+
+    fn decrypt(&self, text: &str) -> Result<String, CipherError> {
+        if !self.supports_decrypt() {
+            return Err(CipherError::Other(
+                "this algorithm does not support decryption".to_string(),
+            ));
+        }
+        self.inverse().encrypt(text)
+    }
+
+    fn inverse(&self) -> Box<dyn Cipher> {
+        // 整体的逆 = 每一层取逆后倒序排列；
+        // 这里直接构造而不经过 then，逆过程不需要再次校验组合合法性
+        Box::new(Pipeline {
+            stages: self
+                .stages
+                .iter()
+                .rev()
+                .map(|stage| stage.inverse())
+                .collect(),
+            configs: vec![None; self.stages.len()],
+        })
+    }
+
+    fn key_requirement(&self) -> KeyRequirement {
+        // 每一层在加入组合前都已带好各自的密钥，组合本身不再需要密钥
+        KeyRequirement::None
+    }
+
+    fn output_is_binary_safe(&self) -> bool {
+        self.stages
+            .last()
+            .is_some_and(|stage| stage.output_is_binary_safe())
+    }
+
+    fn supports_decrypt(&self) -> bool {
+        // 任何一层是单向变换，整体就无法解密
+        self.stages.iter().all(|stage| stage.supports_decrypt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{caesar::Caesar, rail_fence::RailFence, vigenere::Vigenere, xor::Xor};
+
+    #[test]
+    fn test_pipeline_roundtrip() {
+        let pipeline = Pipeline::new()
+            .then(Box::new(Caesar::new(3)))
+            .unwrap()
+            .then(Box::new(Vigenere::new("LEMON").unwrap()))
+            .unwrap()
+            .then(Box::new(RailFence::new(3).unwrap()))
+            .unwrap()
+            .then(Box::new(Xor::new("key").unwrap()))
+            .unwrap();
+        assert_eq!(pipeline.len(), 4);
+
+        let text = "Attack at dawn!";
+        let encrypted = pipeline.encrypt(text).unwrap();
+        assert_eq!(pipeline.decrypt(&encrypted).unwrap(), text);
+        assert!(pipeline.output_is_binary_safe());
+    }
+
+    #[test]
+    fn test_empty_pipeline_is_identity() {
+        let pipeline = Pipeline::new();
+        assert!(pipeline.is_empty());
+        assert_eq!(pipeline.encrypt("hello").unwrap(), "hello");
+        assert!(!pipeline.output_is_binary_safe());
+    }
+
+    #[test]
+    fn test_letter_cipher_after_xor_is_rejected() {
+        let result = Pipeline::new()
+            .then(Box::new(Xor::new("key").unwrap()))
+            .unwrap()
+            .then(Box::new(Caesar::new(3)));
+        assert!(matches!(result, Err(CipherError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_output_is_binary_safe_per_cipher() {
+        assert!(!Caesar::new(3).output_is_binary_safe());
+        assert!(!Vigenere::new("KEY").unwrap().output_is_binary_safe());
+        assert!(!RailFence::new(3).unwrap().output_is_binary_safe());
+        assert!(Xor::new("key").unwrap().output_is_binary_safe());
+    }
+
+    #[test]
+    fn test_named_stages_remember_configs() {
+        let pipeline = Pipeline::new()
+            .then_named("caesar", Some("3"))
+            .unwrap()
+            .then_named("rail_fence", Some("4"))
+            .unwrap();
+        let replayed = Pipeline::from_configs(&pipeline.configs(true).unwrap()).unwrap();
+        let text = "Attack at dawn!";
+        assert_eq!(
+            replayed.encrypt(text).unwrap(),
+            pipeline.encrypt(text).unwrap()
+        );
+
+        // 隐去密钥后只剩算法名
+        let redacted = pipeline.configs(false).unwrap();
+        assert!(redacted.iter().all(|config| config.key.is_none()));
+        assert_eq!(redacted[1].algo, "rail_fence");
+
+        // 直接加入的 cipher 对象无法描述
+        let opaque = pipeline.then(Box::new(Caesar::new(1))).unwrap();
+        assert!(matches!(
+            opaque.configs(true),
+            Err(CipherError::InvalidInput(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_recipe_json_roundtrip() {
+        let json = r#"{"stages":[{"algo":"caesar","key":"3"},{"algo":"rail_fence","key":"4"}]}"#;
+        let pipeline = Pipeline::from_json(json).unwrap();
+        assert_eq!(pipeline.len(), 2);
+        assert_eq!(pipeline.to_json(true).unwrap(), json);
+
+        let text = "Attack at dawn!";
+        let encrypted = pipeline.encrypt(text).unwrap();
+        let reloaded = Pipeline::from_json(&pipeline.to_json(true).unwrap()).unwrap();
+        assert_eq!(reloaded.decrypt(&encrypted).unwrap(), text);
+
+        assert_eq!(
+            pipeline.to_json(false).unwrap(),
+            r#"{"stages":[{"algo":"caesar"},{"algo":"rail_fence"}]}"#
+        );
+        assert!(matches!(
+            Pipeline::from_json("{not json"),
+            Err(CipherError::InvalidInput(_))
+        ));
+    }
+}
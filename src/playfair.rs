@@ -0,0 +1,198 @@
+//! Playfair 密码的实现
+//!
+//! Playfair 以"双字母组"为单位在 5×5 方阵上进行替换：
+//! 同行取右侧字母，同列取下方字母，否则取矩形另外两个角上的字母。
+//!
+//! 注意 Playfair 是有损的：非字母字符会被丢弃，被合并的字母（默认 J）会变成另一个（I），
+//! 并且为了拆分重复字母、补齐长度而插入的填充字母 `X` 在解密后依然保留。
+
+use crate::square::{Merge, Square};
+use crate::vigenere::parse_key;
+use crate::{Cipher, CipherError, Inverse, KeyRequirement, Redacted};
+use std::fmt;
+
+/// 填充字母：拆分双字母组中的重复字母、补齐奇数长度
+const FILLER: u8 = b'X';
+/// 当需要填充的位置本身就是 `X` 时改用的备用填充字母
+const ALT_FILLER: u8 = b'Q';
+
+#[derive(Clone)]
+pub struct Playfair {
+    square: Square,
+}
+
+impl Playfair {
+    /// 创建一个新的 Playfair 密码实例（合并 I/J）
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 关键词，必须非空且仅含 ASCII 字母，否则返回 `CipherError::InvalidKey`
+    pub fn new(key: &str) -> Result<Self, CipherError> {
+        Self::with_merge(key, Merge::IJ)
+    }
+
+    /// 创建一个使用指定合并字母对的 Playfair 实例
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 关键词，规则同 [`Playfair::new`]
+    /// * `merge` - 方阵中合并的字母对，如 [`Merge::CK`]
+    pub fn with_merge(key: &str, merge: Merge) -> Result<Self, CipherError> {
+        let keyword = parse_key(key)?;
+        Ok(Self {
+            square: Square::new(&keyword, merge),
+        })
+    }
+
+    /// 将明文整理为双字母组：丢弃非字母，重复字母之间插入填充字母，奇数长度末尾补齐
+    fn digraphs(&self, text: &str) -> Vec<(u8, u8)> {
+        let letters: Vec<u8> = text
+            .chars()
+            .filter_map(|c| self.square.normalize(c))
+            .collect();
+        let filler_for = |letter: u8| if letter == FILLER { ALT_FILLER } else { FILLER };
+
+        let mut pairs = Vec::with_capacity(letters.len() / 2 + 1);
+        let mut i = 0;
+        while i < letters.len() {
+            let first = letters[i];
+            match letters.get(i + 1) {
+                Some(&second) if second != first => {
+                    pairs.push((first, second));
+                    i += 2;
+                }
+                _ => {
+                    pairs.push((first, filler_for(first)));
+                    i += 1;
+                }
+            }
+        }
+        pairs
+    }
+
+    /// 按 Playfair 规则变换一个双字母组，`step` 为 1 表示加密、4（即 -1 mod 5）表示解密
+    fn transform_pair(&self, (a, b): (u8, u8), step: usize) -> [u8; 2] {
+        let (row_a, col_a) = self.square.position(a);
+        let (row_b, col_b) = self.square.position(b);
+
+        if row_a == row_b {
+            [
+                self.square.at(row_a, (col_a + step) % 5),
+                self.square.at(row_b, (col_b + step) % 5),
+            ]
+        } else if col_a == col_b {
+            [
+                self.square.at((row_a + step) % 5, col_a),
+                self.square.at((row_b + step) % 5, col_b),
+            ]
+        } else {
+            [self.square.at(row_a, col_b), self.square.at(row_b, col_a)]
+        }
+    }
+
+    fn transform(&self, pairs: Vec<(u8, u8)>, step: usize) -> String {
+        pairs
+            .into_iter()
+            .flat_map(|pair| self.transform_pair(pair, step))
+            .map(|b| b as char)
+            .collect()
+    }
+}
+
+// 手动实现 Debug：方阵由关键词生成，直接输出等于泄露密钥
+impl fmt::Debug for Playfair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Playfair")
+            .field("square", &Redacted { len: None })
+            .field("merge", &self.square.merge())
+            .finish()
+    }
+}
+
+impl Cipher for Playfair {
+    /// 加密：输出为不含空格的大写字母
+    fn encrypt(&self, text: &str) -> Result<String, CipherError> {
+        Ok(self.transform(self.digraphs(text), 1))
+    }
+
+    /// 解密：忽略非字母字符；字母个数为奇数或某组两字母相同时返回 `CipherError::InvalidInput`
+    fn decrypt(&self, text: &str) -> Result<String, CipherError> {
+        let letters: Vec<u8> = text
+            .chars()
+            .filter_map(|c| self.square.normalize(c))
+            .collect();
+        if !letters.len().is_multiple_of(2) {
+            return Err(CipherError::InvalidInput(
+                "Playfair ciphertext must contain an even number of letters".to_string(),
+            ));
+        }
+
+        let pairs: Vec<(u8, u8)> = letters.chunks(2).map(|pair| (pair[0], pair[1])).collect();
+        if pairs.iter().any(|(a, b)| a == b) {
+            return Err(CipherError::InvalidInput(
+                "Playfair ciphertext cannot contain a digraph of two identical letters".to_string(),
+            ));
+        }
+
+        Ok(self.transform(pairs, 4))
+    }
+
+    fn inverse(&self) -> Box<dyn Cipher> {
+        Box::new(Inverse(self.clone()))
+    }
+
+    fn key_requirement(&self) -> KeyRequirement {
+        KeyRequirement::Keyword
+    }
+
+    fn rekey(&self, new_key: &str) -> Result<Box<dyn Cipher>, CipherError> {
+        Ok(Box::new(Self::with_merge(new_key, self.square.merge())?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_playfair_encrypt() {
+        let cipher = Playfair::new("PLAYFAIREXAMPLE").unwrap();
+        let encrypted = cipher.encrypt("Hide the gold in the tree stump").unwrap();
+        assert_eq!(encrypted, "BMODZBXDNABEKUDMUIXMMOUVIF");
+    }
+
+    #[test]
+    fn test_playfair_decrypt_keeps_fillers() {
+        let cipher = Playfair::new("PLAYFAIREXAMPLE").unwrap();
+        let decrypted = cipher.decrypt("BMODZBXDNABEKUDMUIXMMOUVIF").unwrap();
+        assert_eq!(decrypted, "HIDETHEGOLDINTHETREXESTUMP");
+    }
+
+    #[test]
+    fn test_playfair_ck_merge_differs_from_default() {
+        let text = "Jack kicked the jukebox";
+        let default = Playfair::new("KEYWORD").unwrap();
+        let merged = Playfair::with_merge("KEYWORD", Merge::CK).unwrap();
+
+        let encrypted_default = default.encrypt(text).unwrap();
+        let encrypted_merged = merged.encrypt(text).unwrap();
+        assert_ne!(encrypted_default, encrypted_merged);
+
+        // 默认方阵中 J 被并入 I；C/K 方阵中保留 J，而 K 被并入 C
+        let decrypted_default = default.decrypt(&encrypted_default).unwrap();
+        let decrypted_merged = merged.decrypt(&encrypted_merged).unwrap();
+        assert!(decrypted_default.starts_with("IACK"));
+        assert!(decrypted_merged.starts_with("JACX"));
+        assert!(!decrypted_merged.contains('K'));
+    }
+
+    #[test]
+    fn test_playfair_rejects_odd_ciphertext() {
+        let result = Playfair::new("KEY").unwrap().decrypt("ABC");
+        assert!(matches!(result, Err(CipherError::InvalidInput(_))));
+        assert!(matches!(
+            Playfair::new("K3Y"),
+            Err(CipherError::InvalidKey(_))
+        ));
+    }
+}
@@ -0,0 +1,127 @@
+//! Polybius 方阵密码的实现
+//!
+//! 每个字母替换为它在 5×5 方阵中的 `行号列号`（均从 1 开始），例如默认方阵中 `A` → `11`、`Z` → `55`。
+//! 编码时各组数字以空格分隔，单词之间以 `/` 分隔。被合并的字母（默认 J）解密后会变成另一个（I）。
+
+use crate::square::{Merge, Square};
+use crate::{Cipher, CipherError, Inverse, KeyRequirement};
+
+/// 单词分隔符
+const WORD_SEPARATOR: &str = "/";
+
+#[derive(Debug, Clone)]
+pub struct Polybius {
+    square: Square,
+}
+
+impl Polybius {
+    /// 创建一个使用标准字母表方阵（合并 I/J）的 Polybius 实例
+    pub fn new() -> Self {
+        Self::with_merge(Merge::IJ)
+    }
+
+    /// 创建一个使用指定合并字母对的 Polybius 实例
+    pub fn with_merge(merge: Merge) -> Self {
+        Self {
+            square: Square::new(b"", merge),
+        }
+    }
+}
+
+impl Default for Polybius {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 解析一组坐标（如 `"23"`），返回从 0 开始的 `(行, 列)`
+fn parse_coordinates(token: &str) -> Option<(usize, usize)> {
+    match token.as_bytes() {
+        [row @ b'1'..=b'5', col @ b'1'..=b'5'] => {
+            Some(((row - b'1') as usize, (col - b'1') as usize))
+        }
+        _ => None,
+    }
+}
+
+impl Cipher for Polybius {
+    /// 编码：空格视为单词分隔；非字母字符返回 `CipherError::InvalidToken`
+    fn encrypt(&self, text: &str) -> Result<String, CipherError> {
+        let mut tokens = Vec::new();
+        for (position, c) in text.chars().enumerate() {
+            if c == ' ' {
+                tokens.push(WORD_SEPARATOR.to_string());
+                continue;
+            }
+            let letter = self
+                .square
+                .normalize(c)
+                .ok_or_else(|| CipherError::InvalidToken {
+                    token: c.to_string(),
+                    position,
+                })?;
+            let (row, col) = self.square.position(letter);
+            tokens.push(format!("{}{}", row + 1, col + 1));
+        }
+        Ok(tokens.join(" "))
+    }
+
+    /// 解码：以空格分隔每组坐标，`/` 表示单词分隔；无法识别的坐标返回 `CipherError::InvalidToken`
+    fn decrypt(&self, text: &str) -> Result<String, CipherError> {
+        let mut plain = String::new();
+        let mut position = 0;
+        for token in text.split(' ') {
+            if token == WORD_SEPARATOR {
+                plain.push(' ');
+            } else {
+                let (row, col) =
+                    parse_coordinates(token).ok_or_else(|| CipherError::InvalidToken {
+                        token: token.to_string(),
+                        position,
+                    })?;
+                plain.push(self.square.at(row, col) as char);
+            }
+            position += token.chars().count() + 1;
+        }
+        Ok(plain)
+    }
+
+    fn inverse(&self) -> Box<dyn Cipher> {
+        Box::new(Inverse(self.clone()))
+    }
+
+    fn key_requirement(&self) -> KeyRequirement {
+        KeyRequirement::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polybius_encode() {
+        let cipher = Polybius::new();
+        assert_eq!(cipher.encrypt("AZ").unwrap(), "11 55");
+        assert_eq!(cipher.encrypt("Hi jo").unwrap(), "23 24 / 24 34");
+    }
+
+    #[test]
+    fn test_polybius_roundtrip_with_uv_merge() {
+        let cipher = Polybius::with_merge(Merge::UV);
+        let encoded = cipher.encrypt("Veni vidi vici").unwrap();
+        assert_eq!(cipher.decrypt(&encoded).unwrap(), "UENI UIDI UICI");
+    }
+
+    #[test]
+    fn test_polybius_reports_bad_token() {
+        let result = Polybius::new().decrypt("11 60 12");
+        assert_eq!(
+            result,
+            Err(CipherError::InvalidToken {
+                token: "60".to_string(),
+                position: 3,
+            })
+        );
+    }
+}
@@ -0,0 +1,33 @@
+//! 常用类型的集中导出
+//!
+//! 通过 `use ciphery::prelude::*;` 一次性引入 [`Cipher`] Trait、[`CipherError`] 以及全部具体算法，
+//! 无需逐个书写 `ciphery::caesar::Caesar` 这样的路径。原有的模块路径依然可用。
+//!
+//! # 示例
+//!
+//! ```
+//! use ciphery::prelude::*;
+//!
+//! let cipher = Caesar::new(3);
+//! assert_eq!(cipher.encrypt("abc").unwrap(), "def");
+//! ```
+
+pub use crate::atbash::Atbash;
+pub use crate::beaufort::Beaufort;
+pub use crate::caesar::Caesar;
+pub use crate::columnar::Columnar;
+pub use crate::feistel::Feistel;
+pub use crate::morse::Morse;
+pub use crate::nato::Nato;
+#[cfg(feature = "normalize")]
+pub use crate::normalize::Normalized;
+#[cfg(feature = "regex")]
+pub use crate::only_matching::OnlyMatching;
+pub use crate::pipeline::Pipeline;
+pub use crate::playfair::Playfair;
+pub use crate::polybius::Polybius;
+pub use crate::rail_fence::RailFence;
+pub use crate::reverse::Reverse;
+pub use crate::vigenere::Vigenere;
+pub use crate::xor::Xor;
+pub use crate::{Cipher, CipherError, Inverse, KeyRequirement};
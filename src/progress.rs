@@ -0,0 +1,135 @@
+//! 进度显示模块
+//!
+//! 处理大文件时，读取与加密可能需要一段时间，本模块提供一个简单的百分比打印器。
+//! 进度信息只写入 stderr，并且只在 stderr 是终端（TTY）时显示，
+//! 这样通过管道重定向输出时，stdout / stderr 都保持干净。
+//!
+//! 目前只有**读取文件**阶段会显示进度：[`ciphery::Cipher`] 的加解密接口一次处理整段文本，
+//! 而换位类算法需要看到全文、Vigenere / XOR 的密钥位置贯穿全文，无法安全地分块处理，
+//! 因此加解密本身没有可以汇报的中间进度。
+
+use std::io::{self, Read, Write};
+
+/// 超过该大小（字节）的文件才会显示进度
+pub const PROGRESS_THRESHOLD: u64 = 1024 * 1024;
+
+/// 每次读取的块大小（字节）
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// 判断是否应当显示进度：文件足够大，且 stderr 是终端
+pub fn should_show(file_size: u64, is_tty: bool) -> bool {
+    is_tty && file_size >= PROGRESS_THRESHOLD
+}
+
+/// 简单的百分比进度打印器
+///
+/// 只有当百分比发生变化时才会重新打印，避免刷屏。
+pub struct Progress<W: Write> {
+    out: W,
+    total: u64,
+    done: u64,
+    last_percent: Option<u64>,
+    enabled: bool,
+}
+
+impl<W: Write> Progress<W> {
+    /// 创建一个新的进度打印器
+    ///
+    /// # 参数
+    ///
+    /// * `out` - 进度输出的目标（通常是 stderr）
+    /// * `total` - 总字节数
+    /// * `enabled` - 为 `false` 时不产生任何输出
+    pub fn new(out: W, total: u64, enabled: bool) -> Self {
+        Self {
+            out,
+            total,
+            done: 0,
+            last_percent: None,
+            enabled,
+        }
+    }
+
+    /// 记录新处理的 `n` 个字节，并在百分比变化时刷新显示
+    pub fn advance(&mut self, n: u64) {
+        self.done += n;
+        if !self.enabled || self.total == 0 {
+            return;
+        }
+
+        let percent = (self.done.min(self.total) * 100) / self.total;
+        if self.last_percent != Some(percent) {
+            self.last_percent = Some(percent);
+            // 进度显示失败不应影响主流程，因此忽略写入错误
+            let _ = write!(self.out, "\r[progress] {:>3}%", percent);
+            let _ = self.out.flush();
+        }
+    }
+
+    /// 结束进度显示（换行，让后续输出从新的一行开始）
+    pub fn finish(&mut self) {
+        if self.enabled && self.last_percent.is_some() {
+            let _ = writeln!(self.out);
+        }
+    }
+}
+
+/// 按块读取全部内容，每读完一块就更新一次进度
+pub fn read_with_progress<R: Read, W: Write>(
+    mut reader: R,
+    progress: &mut Progress<W>,
+) -> io::Result<Vec<u8>> {
+    let mut content = Vec::new();
+    let mut buf = vec![0u8; CHUNK_SIZE];
+
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        content.extend_from_slice(&buf[..n]);
+        progress.advance(n as u64);
+    }
+
+    progress.finish();
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_should_show() {
+        assert!(should_show(PROGRESS_THRESHOLD, true));
+        assert!(!should_show(PROGRESS_THRESHOLD, false));
+        assert!(!should_show(PROGRESS_THRESHOLD - 1, true));
+    }
+
+    #[test]
+    fn test_non_tty_produces_no_output() {
+        let data = vec![b'a'; (PROGRESS_THRESHOLD * 2) as usize];
+        let mut out = Vec::new();
+        let enabled = should_show(data.len() as u64, false);
+        let mut progress = Progress::new(&mut out, data.len() as u64, enabled);
+
+        let content = read_with_progress(Cursor::new(&data), &mut progress).unwrap();
+
+        assert_eq!(content, data);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_tty_reports_completion() {
+        let data = vec![b'a'; (PROGRESS_THRESHOLD * 2) as usize];
+        let mut out = Vec::new();
+        let mut progress = Progress::new(&mut out, data.len() as u64, true);
+
+        read_with_progress(Cursor::new(&data), &mut progress).unwrap();
+
+        let printed = String::from_utf8(out).unwrap();
+        assert!(printed.contains("[progress] 100%"));
+        assert!(printed.ends_with('\n'));
+    }
+}
@@ -1,28 +1,129 @@
 // Rail Fence Cipher implementation in Rust
 // Zig Zag
-use crate::{Cipher, CipherError};
+use crate::{Cipher, CipherError, Inverse, KeyRequirement};
 
-#[derive(Debug)]
+/// 之字形 (zigzag) 起始时的移动方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+	/// 向下（朝最后一层）移动
+	Down,
+	/// 向上（朝第 0 层）移动
+	Up,
+}
+
+/// Rail Fence（栅栏）密码
+///
+/// # 空格策略
+///
+/// 栅栏密码只重排字符，不改变字符本身，因此空格也会和字母一起参与之字形换位。
+/// "带空格换位"与"去掉空格后换位"得到的密文完全不同，两端必须使用同一种策略：
+///
+/// * 保留空格（默认）：空格被当作普通字符参与换位，加密后再解密可以完整还原原文。
+/// * 去除空格（`with_preserve_spaces(false)`）：加密前先删除所有空格，解密前同样删除密文中的空格
+///   （例如为了便于阅读而分组书写的密文）。这是**有损**的：密文中已经没有空格的位置信息，
+///   解密只能得到不含空格的明文，无法把空格插回原处。
+///
+/// # 层数不少于文本长度
+///
+/// 层数 >= 文本字符数时，之字形还没折返就已经写完了所有字符，每层最多只有一个字符，
+/// 部分层保持为空。此时加密结果就是原文（从中途层出发时只是简单的重排），
+/// 这里**不会**把层数截断为文本长度：密文与其他按原始层数实现的工具保持一致，解密依然是加密的逆运算。
+/// 命令行会对这种情况打印 `[warning]`，提示层数相对文本过多，文本没有（或几乎没有）被打乱。
+#[derive(Debug, Clone)]
 pub struct RailFence {
 	rails: usize,
+	start_rail: usize,
+	start_direction: Direction,
+	preserve_spaces: bool,
 }
 
 impl RailFence {
 	pub fn new(rails: usize) -> Result<Self, CipherError> {
+		Self::with_offset(rails, 0, Direction::Down)
+	}
+
+	/// 创建一个从指定层、指定方向开始走之字形的 Rail Fence 实例
+	///
+	/// 部分教材/工具的实现并不从第 0 层向下开始，而是从之字形的中途开始，
+	/// 使用此构造函数即可与这些变体保持一致。`with_offset(rails, 0, Direction::Down)` 等价于 `new(rails)`。
+	///
+	/// 到达第 0 层或最后一层时方向总会反转，因此从第 0 层"向上"出发与"向下"出发效果相同。
+	///
+	/// # 参数
+	///
+	/// * `rails` - 栅栏层数，必须 >= 2
+	/// * `start_rail` - 起始层（从 0 开始），必须 < `rails`
+	/// * `start_direction` - 起始移动方向
+	pub fn with_offset(
+		rails: usize,
+		start_rail: usize,
+		start_direction: Direction,
+	) -> Result<Self, CipherError> {
 		if rails < 2 {
 			return Err(CipherError::InvalidKey(
 				"Rail Fence rails must be >= 2".to_string(),
 			));
 		}
+		if start_rail >= rails {
+			return Err(CipherError::InvalidKey(format!(
+				"Rail Fence start rail must be < {}",
+				rails
+			)));
+		}
+
+		Ok(Self {
+			rails,
+			start_rail,
+			start_direction,
+			preserve_spaces: true,
+		})
+	}
+
+	/// 设置空格策略：`true`（默认）保留空格参与换位，`false` 在加解密前去除所有空格
+	///
+	/// 去除空格是有损的，详见 [`RailFence`] 的"空格策略"说明。
+	pub fn with_preserve_spaces(mut self, preserve_spaces: bool) -> Self {
+		self.preserve_spaces = preserve_spaces;
+		self
+	}
+
+	/// 按空格策略预处理输入文本（加密与解密共用，保证两端一致）
+	fn prepare(&self, text: &str) -> String {
+		if self.preserve_spaces {
+			text.to_string()
+		} else {
+			text.chars().filter(|&c| c != ' ').collect()
+		}
+	}
+
+	/// 栅栏层数
+	pub fn rails(&self) -> usize {
+		self.rails
+	}
 
-		Ok(Self { rails })
+	/// 对 `len` 个字符的文本，加密是否不改变任何字符的位置
+	///
+	/// 之字形轨迹的层号从不下降时（例如从第 0 层出发、层数不少于文本长度），
+	/// 逐层读出的顺序就是原文顺序。
+	pub fn leaves_unchanged(&self, len: usize) -> bool {
+		self.pattern(len).windows(2).all(|pair| pair[0] <= pair[1])
+	}
+
+	fn pattern(&self, text_len: usize) -> Vec<usize> {
+		build_pattern(text_len, self.rails, self.start_rail, self.start_direction)
 	}
 }
 
-fn build_pattern(text_len: usize, rails: usize) -> Vec<usize> {
+/// 生成每个字符所在的层号序列（之字形轨迹）
+fn build_pattern(
+	text_len: usize,
+	rails: usize,
+	start_rail: usize,
+	start_direction: Direction,
+) -> Vec<usize> {
 	let mut pattern = Vec::with_capacity(text_len);
-	let mut rail = 0usize;
-	let mut direction_down = true;
+	let mut rail = start_rail;
+	let mut direction_down = start_direction == Direction::Down;
 
 	for _ in 0..text_len {
 		pattern.push(rail);
@@ -40,12 +141,24 @@ fn build_pattern(text_len: usize, rails: usize) -> Vec<usize> {
 }
 
 pub fn encrypt(text: &str, rails: usize) -> String {
+	encrypt_with_pattern(text, rails, |len| build_pattern(len, rails, 0, Direction::Down))
+}
+
+pub fn decrypt(text: &str, rails: usize) -> String {
+	decrypt_with_pattern(text, rails, |len| build_pattern(len, rails, 0, Direction::Down))
+}
+
+fn encrypt_with_pattern(
+	text: &str,
+	rails: usize,
+	pattern: impl Fn(usize) -> Vec<usize>,
+) -> String {
 	if text.chars().count() <= 1 {
 		return text.to_string();
 	}
 
 	let mut rows = vec![String::new(); rails];
-	let pattern = build_pattern(text.chars().count(), rails);
+	let pattern = pattern(text.chars().count());
 
 	for (ch, rail) in text.chars().zip(pattern.iter().copied()) {
 		rows[rail].push(ch);
@@ -54,7 +167,11 @@ pub fn encrypt(text: &str, rails: usize) -> String {
 	rows.concat()
 }
 
-pub fn decrypt(text: &str, rails: usize) -> String {
+fn decrypt_with_pattern(
+	text: &str,
+	rails: usize,
+	pattern: impl Fn(usize) -> Vec<usize>,
+) -> String {
 	let chars: Vec<char> = text.chars().collect();
 	let text_len = chars.len();
 
@@ -62,28 +179,26 @@ pub fn decrypt(text: &str, rails: usize) -> String {
 		return text.to_string();
 	}
 
-	let pattern = build_pattern(text_len, rails);
+	let pattern = pattern(text_len);
 
-	let mut rail_counts = vec![0usize; rails];
+	// 密文是各层依次拼接而成：先统计每层的字符数，
+	// 再把它转换为每层在 chars 中的起始下标，作为该层的读取游标
+	let mut cursors = vec![0usize; rails];
 	for rail in &pattern {
-		rail_counts[*rail] += 1;
+		cursors[*rail] += 1;
 	}
-
-	let mut rails_chars: Vec<Vec<char>> = Vec::with_capacity(rails);
-	let mut cursor = 0usize;
-	for count in rail_counts {
-		let segment = chars[cursor..cursor + count].to_vec();
-		rails_chars.push(segment);
-		cursor += count;
+	let mut start = 0usize;
+	for cursor in cursors.iter_mut() {
+		let count = *cursor;
+		*cursor = start;
+		start += count;
 	}
 
-	let mut rail_positions = vec![0usize; rails];
-	let mut plain = String::with_capacity(text_len);
-
+	// 沿之字形轨迹依次从对应层的游标处取字符，无需把每一层复制成单独的 Vec
+	let mut plain = String::with_capacity(text.len());
 	for rail in pattern {
-		let pos = rail_positions[rail];
-		plain.push(rails_chars[rail][pos]);
-		rail_positions[rail] += 1;
+		plain.push(chars[cursors[rail]]);
+		cursors[rail] += 1;
 	}
 
 	plain
@@ -91,11 +206,31 @@ pub fn decrypt(text: &str, rails: usize) -> String {
 
 impl Cipher for RailFence {
 	fn encrypt(&self, text: &str) -> Result<String, CipherError> {
-		Ok(encrypt(text, self.rails))
+		let text = self.prepare(text);
+		Ok(encrypt_with_pattern(&text, self.rails, |len| self.pattern(len)))
 	}
 
 	fn decrypt(&self, text: &str) -> Result<String, CipherError> {
-		Ok(decrypt(text, self.rails))
+		let text = self.prepare(text);
+		Ok(decrypt_with_pattern(&text, self.rails, |len| self.pattern(len)))
+	}
+
+	fn inverse(&self) -> Box<dyn Cipher> {
+		Box::new(Inverse(self.clone()))
+	}
+
+	fn key_requirement(&self) -> KeyRequirement {
+		KeyRequirement::RailCount
+	}
+
+	fn rekey(&self, new_key: &str) -> Result<Box<dyn Cipher>, CipherError> {
+		let rails: usize = new_key.parse().map_err(|_| {
+			CipherError::InvalidKey("Rail Fence key must be a number >= 2".to_string())
+		})?;
+		// 起始层与方向沿用；起始层超出新的层数时 with_offset 会报错
+		let cipher = Self::with_offset(rails, self.start_rail, self.start_direction)?
+			.with_preserve_spaces(self.preserve_spaces);
+		Ok(Box::new(cipher))
 	}
 }
 
@@ -128,12 +263,40 @@ mod tests {
 		assert_eq!(decrypted, input);
 	}
 
+	#[test]
+	fn test_rail_fence_more_rails_than_chars() {
+		// 5 层、3 个字符：每层至多一个字符，从第 0 层出发时密文与原文相同
+		let cipher = RailFence::new(5).unwrap();
+		assert_eq!(cipher.encrypt("abc").unwrap(), "abc");
+		assert_eq!(cipher.decrypt("abc").unwrap(), "abc");
+
+		// 从中途层出发时会发生重排，解密依然能还原
+		let cipher = RailFence::with_offset(5, 3, Direction::Up).unwrap();
+		let encrypted = cipher.encrypt("abc").unwrap();
+		assert_eq!(encrypted, "cba");
+		assert_eq!(cipher.decrypt(&encrypted).unwrap(), "abc");
+		assert!(!cipher.leaves_unchanged(3));
+		assert!(RailFence::new(5).unwrap().leaves_unchanged(3));
+		assert!(!RailFence::new(2).unwrap().leaves_unchanged(3));
+	}
+
 	#[test]
 	fn test_rail_fence_two_rails() {
 		assert_eq!(encrypt("HELLO", 2), "HLOEL");
 		assert_eq!(decrypt("HLOEL", 2), "HELLO");
 	}
 
+	#[test]
+	fn test_rail_fence_decrypt_large_input() {
+		// 大段混合 ASCII / Unicode 文本，覆盖多种层数与起始偏移
+		let input = "WE ARE DISCOVERED, flee at once! 世界 🦀\n".repeat(2_000);
+		for rails in [2, 3, 7, 50] {
+			assert_eq!(decrypt(&encrypt(&input, rails), rails), input);
+			let cipher = RailFence::with_offset(rails, 1, Direction::Up).unwrap();
+			assert_eq!(cipher.decrypt(&cipher.encrypt(&input).unwrap()).unwrap(), input);
+		}
+	}
+
 	#[test]
 	fn test_rail_fence_invalid_rails() {
 		let result = RailFence::new(1);
@@ -143,4 +306,70 @@ mod tests {
 			CipherError::InvalidKey("Rail Fence rails must be >= 2".to_string())
 		);
 	}
+
+	#[test]
+	fn test_rail_fence_zero_offset_matches_default() {
+		let input = "WEAREDISCOVEREDFLEEATONCE";
+		let default = RailFence::new(3).unwrap();
+		let offset = RailFence::with_offset(3, 0, Direction::Down).unwrap();
+		assert_eq!(offset.encrypt(input).unwrap(), default.encrypt(input).unwrap());
+	}
+
+	#[test]
+	fn test_rail_fence_offset_roundtrip() {
+		let input = "WEAREDISCOVEREDFLEEATONCE";
+		let default = RailFence::new(3).unwrap().encrypt(input).unwrap();
+
+		let offsets = [(1, Direction::Down), (1, Direction::Up), (2, Direction::Up)];
+		for (start_rail, direction) in offsets {
+			let cipher = RailFence::with_offset(3, start_rail, direction).unwrap();
+			let encrypted = cipher.encrypt(input).unwrap();
+			assert_ne!(encrypted, default);
+			assert_eq!(cipher.decrypt(&encrypted).unwrap(), input);
+		}
+	}
+
+	#[test]
+	fn test_rail_fence_invalid_start_rail() {
+		let result = RailFence::with_offset(3, 3, Direction::Down);
+		assert_eq!(
+			result.unwrap_err(),
+			CipherError::InvalidKey("Rail Fence start rail must be < 3".to_string())
+		);
+	}
+
+	#[test]
+	fn test_rail_fence_preserve_spaces_roundtrip() {
+		let input = "WE ARE DISCOVERED";
+		let cipher = RailFence::new(3).unwrap().with_preserve_spaces(true);
+		let encrypted = cipher.encrypt(input).unwrap();
+		assert_eq!(encrypted.matches(' ').count(), 2);
+		assert_eq!(cipher.decrypt(&encrypted).unwrap(), input);
+	}
+
+	#[test]
+	fn test_rail_fence_strip_spaces_is_lossy() {
+		let cipher = RailFence::new(3).unwrap().with_preserve_spaces(false);
+		let encrypted = cipher.encrypt("WE ARE DISCOVERED FLEE AT ONCE").unwrap();
+		assert_eq!(encrypted, "WECRLTEERDSOEEFEAOCAIVDEN");
+		// 空格无法还原
+		assert_eq!(cipher.decrypt(&encrypted).unwrap(), "WEAREDISCOVEREDFLEEATONCE");
+		// 分组书写的密文在解密前同样会去掉空格
+		assert_eq!(
+			cipher.decrypt("WECRL TEERD SOEEF EAOCA IVDEN").unwrap(),
+			"WEAREDISCOVEREDFLEEATONCE"
+		);
+	}
+
+	#[test]
+	fn test_rail_fence_space_policies_differ() {
+		let input = "WE ARE DISCOVERED";
+		let preserved = RailFence::new(3).unwrap().encrypt(input).unwrap();
+		let stripped = RailFence::new(3)
+			.unwrap()
+			.with_preserve_spaces(false)
+			.encrypt(input)
+			.unwrap();
+		assert_ne!(preserved.replace(' ', ""), stripped);
+	}
 }
@@ -0,0 +1,281 @@
+//! 算法注册表 (Cipher Registry)
+//!
+//! 把算法名映射到"构造函数"闭包，使嵌入方无需修改 `ciphery` 的源码就能在运行时加入自定义算法。
+//! 注册表预先装入全部内置算法；[`crate::build_cipher`]（以及基于它的 WASM 接口）
+//! 通过全局注册表按名称构造算法，因此经 [`register`] 注册的算法可以像内置算法一样使用。
+//!
+//! ```
+//! use ciphery::registry;
+//! use ciphery::reverse::Reverse;
+//!
+//! registry::register("mirror", |_key| Ok(Box::new(Reverse::new())));
+//! let cipher = ciphery::build_cipher("mirror", "").unwrap();
+//! assert_eq!(cipher.encrypt("hello").unwrap(), "olleh");
+//! ```
+
+use crate::{
+    Cipher, CipherError, atbash, beaufort, caesar, columnar, feistel, morse, nato, playfair,
+    polybius, rail_fence, reverse, vigenere, xor,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
+
+/// 构造函数：接收可选的密钥字符串，返回构造好的算法
+///
+/// 无密钥的算法可以忽略参数；需要密钥的算法在缺少密钥或密钥不合法时应返回 `CipherError::InvalidKey`。
+pub type Constructor =
+    Arc<dyn Fn(Option<&str>) -> Result<Box<dyn Cipher>, CipherError> + Send + Sync>;
+
+/// 算法名到构造函数的映射
+#[derive(Clone, Default)]
+pub struct CipherRegistry {
+    constructors: HashMap<String, Constructor>,
+}
+
+impl CipherRegistry {
+    /// 创建一个空的注册表（不含任何算法）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建一个预先装入全部内置算法的注册表
+    ///
+    /// 名称与 [`crate::build_cipher`] 文档中列出的算法名一致。
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register("caesar", |key| {
+            let shift = caesar::parse_key(key.unwrap_or(""))?;
+            Ok(Box::new(caesar::Caesar::new((shift % 26) as u8)))
+        });
+        registry.register("rot13", |_| Ok(Box::new(caesar::Caesar::rot13())));
+        registry.register("atbash", |_| Ok(Box::new(atbash::Atbash::new())));
+        registry.register("morse", |_| Ok(Box::new(morse::Morse::new())));
+        registry.register("nato", |_| Ok(Box::new(nato::Nato::new())));
+        registry.register("polybius", |_| Ok(Box::new(polybius::Polybius::new())));
+        registry.register("reverse", |_| Ok(Box::new(reverse::Reverse::new())));
+        registry.register("reverse_words", |_| Ok(Box::new(reverse::Reverse::words())));
+        registry.register("playfair", |key| {
+            Ok(Box::new(playfair::Playfair::new(key.unwrap_or(""))?))
+        });
+        registry.register("vigenere", |key| {
+            Ok(Box::new(vigenere::Vigenere::new(key.unwrap_or(""))?))
+        });
+        registry.register("beaufort", |key| {
+            Ok(Box::new(beaufort::Beaufort::new(key.unwrap_or(""))?))
+        });
+        registry.register("columnar", |key| {
+            Ok(Box::new(columnar::Columnar::new(key.unwrap_or(""))?))
+        });
+        registry.register("xor", |key| Ok(Box::new(xor::Xor::new(key.unwrap_or(""))?)));
+        registry.register("feistel", |key| {
+            let (key, rounds) = parse_feistel_key(key.unwrap_or(""));
+            Ok(Box::new(feistel::Feistel::new(key, rounds)?))
+        });
+        registry.register("rail_fence", |key| {
+            let rails: usize = key.unwrap_or("").parse().map_err(|_| {
+                CipherError::InvalidKey("Rail Fence key must be a number >= 2".to_string())
+            })?;
+            Ok(Box::new(rail_fence::RailFence::new(rails)?))
+        });
+        registry
+    }
+
+    /// 注册一个算法；同名算法（包括内置算法）会被覆盖
+    pub fn register<F>(&mut self, name: &str, constructor: F)
+    where
+        F: Fn(Option<&str>) -> Result<Box<dyn Cipher>, CipherError> + Send + Sync + 'static,
+    {
+        self.constructors
+            .insert(name.to_string(), Arc::new(constructor));
+    }
+
+    /// 查找某个名称对应的构造函数
+    pub fn get(&self, name: &str) -> Option<Constructor> {
+        self.constructors.get(name).cloned()
+    }
+
+    /// 是否注册了某个算法
+    pub fn contains(&self, name: &str) -> bool {
+        self.constructors.contains_key(name)
+    }
+
+    /// 按名称构造算法，未注册的名称返回 `CipherError::InvalidInput`
+    pub fn build(&self, name: &str, key: Option<&str>) -> Result<Box<dyn Cipher>, CipherError> {
+        match self.get(name) {
+            Some(constructor) => constructor(key),
+            None => Err(unsupported(name)),
+        }
+    }
+
+    /// 已注册的全部算法名，按字母顺序排列
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.constructors.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+/// 全局注册表，首次使用时装入内置算法
+static GLOBAL: LazyLock<RwLock<CipherRegistry>> =
+    LazyLock::new(|| RwLock::new(CipherRegistry::with_builtins()));
+
+/// 向全局注册表注册一个算法，之后即可通过 [`crate::build_cipher`] 按名称构造
+///
+/// 同名算法（包括内置算法）会被覆盖。
+pub fn register<F>(name: &str, constructor: F)
+where
+    F: Fn(Option<&str>) -> Result<Box<dyn Cipher>, CipherError> + Send + Sync + 'static,
+{
+    GLOBAL
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .register(name, constructor);
+}
+
+/// 使用全局注册表按名称构造算法
+pub fn build(name: &str, key: Option<&str>) -> Result<Box<dyn Cipher>, CipherError> {
+    // 先取出构造函数再释放锁，构造函数内部可以放心地再次调用 `build`（例如组合其他算法）
+    let constructor = GLOBAL.read().unwrap_or_else(|e| e.into_inner()).get(name);
+    match constructor {
+        Some(constructor) => constructor(key),
+        None => Err(unsupported(name)),
+    }
+}
+
+/// 全局注册表中的全部算法名，按字母顺序排列
+pub fn names() -> Vec<String> {
+    GLOBAL.read().unwrap_or_else(|e| e.into_inner()).names()
+}
+
+/// 解析 Feistel 的 `密钥[:轮数]`：末尾是 `:数字` 时取作轮数，否则使用 [`feistel::DEFAULT_ROUNDS`]
+///
+/// 密钥本身以 `:数字` 结尾时需要显式写出轮数，例如 `a:1:16`。
+fn parse_feistel_key(key: &str) -> (&str, usize) {
+    match key.rsplit_once(':') {
+        Some((key, rounds)) if !rounds.is_empty() && rounds.bytes().all(|b| b.is_ascii_digit()) => {
+            (key, rounds.parse().unwrap_or(usize::MAX))
+        }
+        _ => (key, feistel::DEFAULT_ROUNDS),
+    }
+}
+
+fn unsupported(name: &str) -> CipherError {
+    CipherError::InvalidInput(format!("Algorithm '{}' is not supported", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::KeyRequirement;
+
+    /// 把字母转成大写的"玩具算法"，解密时无法还原，只用于验证注册流程
+    struct Upper;
+
+    impl Cipher for Upper {
+        fn encrypt(&self, text: &str) -> Result<String, CipherError> {
+            Ok(text.to_uppercase())
+        }
+
+        fn decrypt(&self, text: &str) -> Result<String, CipherError> {
+            Ok(text.to_lowercase())
+        }
+
+        fn inverse(&self) -> Box<dyn Cipher> {
+            Box::new(Upper)
+        }
+
+        fn key_requirement(&self) -> KeyRequirement {
+            KeyRequirement::None
+        }
+    }
+
+    #[test]
+    fn test_builtins_are_registered() {
+        let registry = CipherRegistry::with_builtins();
+        for name in [
+            "caesar",
+            "rot13",
+            "vigenere",
+            "xor",
+            "rail_fence",
+            "reverse",
+        ] {
+            assert!(registry.contains(name), "{}", name);
+        }
+        let cipher = registry.build("caesar", Some("3")).unwrap();
+        assert_eq!(cipher.encrypt("hello").unwrap(), "khoor");
+        // 与命令行一致，也接受按 A=0 换算的单个字母
+        let cipher = registry.build("caesar", Some("D")).unwrap();
+        assert_eq!(cipher.encrypt("hello").unwrap(), "khoor");
+        assert!(matches!(
+            registry.build("vigenere", None),
+            Err(CipherError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            CipherRegistry::new().build("caesar", Some("3")),
+            Err(CipherError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_feistel_key_with_optional_rounds() {
+        assert_eq!(
+            parse_feistel_key("secret"),
+            ("secret", feistel::DEFAULT_ROUNDS)
+        );
+        assert_eq!(parse_feistel_key("secret:8"), ("secret", 8));
+        assert_eq!(parse_feistel_key("a:b"), ("a:b", feistel::DEFAULT_ROUNDS));
+        assert_eq!(parse_feistel_key("a:1:16"), ("a:1", 16));
+
+        let registry = CipherRegistry::with_builtins();
+        let cipher = registry.build("feistel", Some("secret:8")).unwrap();
+        let encrypted = cipher.encrypt("hello").unwrap();
+        assert_eq!(
+            encrypted,
+            feistel::Feistel::new("secret", 8)
+                .unwrap()
+                .encrypt("hello")
+                .unwrap()
+        );
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), "hello");
+        // 轮数超出范围（包括溢出 usize 的数字）与空密钥都是密钥错误
+        for key in ["secret:0", "secret:99999999999999999999999", ":8"] {
+            assert!(
+                matches!(
+                    registry.build("feistel", Some(key)),
+                    Err(CipherError::InvalidKey(_))
+                ),
+                "{}",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn test_register_custom_cipher() {
+        let mut registry = CipherRegistry::new();
+        registry.register("upper", |_| Ok(Box::new(Upper)));
+        assert_eq!(registry.names(), ["upper"]);
+        let cipher = registry.build("upper", None).unwrap();
+        assert_eq!(cipher.encrypt("hello").unwrap(), "HELLO");
+    }
+
+    #[test]
+    fn test_global_register_flows_through_build_cipher() {
+        register("test_upper", |_| Ok(Box::new(Upper)));
+        assert!(names().contains(&"test_upper".to_string()));
+        let cipher = crate::build_cipher("test_upper", "").unwrap();
+        assert_eq!(cipher.encrypt("hello").unwrap(), "HELLO");
+
+        // 构造函数内部再次使用全局注册表也不会死锁
+        register("test_double_caesar", |key| {
+            let first = build("caesar", key)?;
+            let second = build("caesar", key)?;
+            Ok(Box::new(
+                crate::pipeline::Pipeline::new().then(first)?.then(second)?,
+            ))
+        });
+        let cipher = crate::build_cipher("test_double_caesar", "1").unwrap();
+        assert_eq!(cipher.encrypt("abc").unwrap(), "cde");
+    }
+}
@@ -0,0 +1,138 @@
+//! 反转 (Reverse) 密码的实现
+//!
+//! 最简单的"密码"之一：把整段文本倒过来写，或者只把单词的顺序倒过来。没有密钥，
+//! 两种方式都是自反的：再反转一次即可还原。
+//!
+//! 整段反转以**字素簇**（grapheme cluster，即用户眼中的"一个字符"）为单位，
+//! 因此 `"e\u{301}"`（e + 组合重音符）这样的组合字符、带肤色修饰的 emoji 都不会被拆开。
+//!
+//! 例外：如果文本以一个孤立的组合符开头（前面没有可依附的基础字符），反转后它会落到末尾，
+//! 并与前一个字符组成新的字素簇，因此再反转一次**无法**还原原文。
+
+use crate::{Cipher, CipherError, KeyRequirement};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// 反转的粒度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Unit {
+    /// 按字素簇反转整段文本（默认）
+    #[default]
+    Graphemes,
+    /// 只反转以空白分隔的单词的顺序，单词内部保持不变
+    Words,
+}
+
+/// 反转密码（无密钥）
+#[derive(Debug, Clone, Default)]
+pub struct Reverse {
+    unit: Unit,
+}
+
+impl Reverse {
+    /// 创建一个按字素簇反转整段文本的实例：`"hello"` ↔ `"olleh"`
+    pub fn new() -> Self {
+        Self {
+            unit: Unit::Graphemes,
+        }
+    }
+
+    /// 创建一个只反转单词顺序的实例：`"attack at dawn"` ↔ `"dawn at attack"`
+    ///
+    /// 单词之间的空白（包括连续的空格、换行）作为整体随之移动，因此再反转一次可以完整还原。
+    pub fn words() -> Self {
+        Self { unit: Unit::Words }
+    }
+
+    /// 执行反转（加密与解密共用）
+    fn transform(&self, text: &str) -> String {
+        match self.unit {
+            Unit::Graphemes => text.graphemes(true).rev().collect(),
+            Unit::Words => split_runs(text).into_iter().rev().collect(),
+        }
+    }
+}
+
+/// 将文本切分为交替出现的"空白段"与"非空白段"
+fn split_runs(text: &str) -> Vec<&str> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    let mut in_whitespace = None;
+    for (index, c) in text.char_indices() {
+        let is_whitespace = c.is_whitespace();
+        if in_whitespace.is_some_and(|previous| previous != is_whitespace) {
+            runs.push(&text[start..index]);
+            start = index;
+        }
+        in_whitespace = Some(is_whitespace);
+    }
+    if start < text.len() {
+        runs.push(&text[start..]);
+    }
+    runs
+}
+
+impl Cipher for Reverse {
+    fn encrypt(&self, text: &str) -> Result<String, CipherError> {
+        Ok(self.transform(text))
+    }
+
+    fn decrypt(&self, text: &str) -> Result<String, CipherError> {
+        // 自反密码：解密就是再反转一次
+        Ok(self.transform(text))
+    }
+
+    fn inverse(&self) -> Box<dyn Cipher> {
+        Box::new(self.clone())
+    }
+
+    fn key_requirement(&self) -> KeyRequirement {
+        KeyRequirement::None
+    }
+
+    // 以孤立组合符开头的文本除外，见模块文档
+    fn is_reciprocal(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reverse_string() {
+        let cipher = Reverse::new();
+        assert_eq!(cipher.encrypt("hello").unwrap(), "olleh");
+        assert_eq!(cipher.decrypt("olleh").unwrap(), "hello");
+    }
+
+    #[test]
+    fn test_reverse_keeps_combining_marks_attached() {
+        // "café" 中的 é 由 e + U+0301 组合而成，按 char 反转会把重音符挂到错误的字母上
+        let text = "cafe\u{301} 👍🏽!";
+        let encrypted = Reverse::new().encrypt(text).unwrap();
+        assert_eq!(encrypted, "!👍🏽 e\u{301}fac");
+        assert_eq!(Reverse::new().decrypt(&encrypted).unwrap(), text);
+    }
+
+    #[test]
+    fn test_reverse_leading_combining_mark_does_not_round_trip() {
+        // 开头的孤立重音符反转后挂到了 `a` 上，再反转一次得到的是另一段文本
+        let cipher = Reverse::new();
+        let encrypted = cipher.encrypt("\u{301}ab").unwrap();
+        assert_eq!(encrypted, "ba\u{301}");
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), "a\u{301}b");
+    }
+
+    #[test]
+    fn test_reverse_words() {
+        let cipher = Reverse::words();
+        assert_eq!(cipher.encrypt("attack at dawn").unwrap(), "dawn at attack");
+
+        // 连续空白与换行随单词一起移动，再反转一次即可完整还原
+        let text = "  first  second\nthird ";
+        let encrypted = cipher.encrypt(text).unwrap();
+        assert_eq!(encrypted, " third\nsecond  first  ");
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), text);
+    }
+}
@@ -0,0 +1,163 @@
+//! 5×5 字母方阵 (Polybius square) 辅助模块
+//!
+//! Playfair、Polybius 等方阵密码都需要把 26 个字母放进 25 个格子，
+//! 因此必须把其中两个字母合并为一个。本模块统一提供方阵的构造与"合并字母对"的配置。
+
+use crate::CipherError;
+use std::fmt;
+use std::str::FromStr;
+
+/// 方阵中合并的一对字母：后一个字母在加密前一律替换为前一个
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Merge {
+    /// J 并入 I（最常见的约定，默认）
+    #[default]
+    IJ,
+    /// K 并入 C
+    CK,
+    /// V 并入 U
+    UV,
+}
+
+impl Merge {
+    /// 返回 `(保留的字母, 被并入的字母)`，均为大写
+    pub fn pair(&self) -> (u8, u8) {
+        match self {
+            Merge::IJ => (b'I', b'J'),
+            Merge::CK => (b'C', b'K'),
+            Merge::UV => (b'U', b'V'),
+        }
+    }
+}
+
+impl fmt::Display for Merge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (kept, dropped) = self.pair();
+        write!(f, "{}/{}", kept as char, dropped as char)
+    }
+}
+
+/// 从字符串解析合并字母对，接受 `IJ`、`I/J`、`ij` 等写法（两个字母顺序不限）；
+/// 不支持的组合返回 `CipherError::InvalidKey`
+impl FromStr for Merge {
+    type Err = CipherError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut letters: Vec<u8> = s
+            .bytes()
+            .filter(|b| *b != b'/')
+            .map(|b| b.to_ascii_uppercase())
+            .collect();
+        letters.sort_unstable();
+        match letters.as_slice() {
+            b"IJ" => Ok(Merge::IJ),
+            b"CK" => Ok(Merge::CK),
+            b"UV" => Ok(Merge::UV),
+            _ => Err(CipherError::InvalidKey(format!(
+                "unsupported letter merge '{}' (expected I/J, C/K or U/V)",
+                s
+            ))),
+        }
+    }
+}
+
+/// 由关键词与合并规则生成的 5×5 字母方阵
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Square {
+    /// 按行排列的 25 个大写字母
+    cells: [u8; 25],
+    merge: Merge,
+}
+
+impl Square {
+    /// 生成方阵：先依次填入关键词中的字母（去重），再按字母表顺序补齐剩余字母
+    ///
+    /// # 参数
+    ///
+    /// * `keyword` - 已校验过的大写字母关键词，可以为空（即标准字母表方阵）
+    /// * `merge` - 合并的字母对
+    pub fn new(keyword: &[u8], merge: Merge) -> Self {
+        let (kept, dropped) = merge.pair();
+        let mut cells = [0u8; 25];
+        let mut used = [false; 26];
+        let mut len = 0;
+
+        for letter in keyword.iter().copied().chain(b'A'..=b'Z') {
+            let letter = if letter == dropped { kept } else { letter };
+            let index = (letter - b'A') as usize;
+            if !used[index] {
+                used[index] = true;
+                cells[len] = letter;
+                len += 1;
+            }
+        }
+
+        Self { cells, merge }
+    }
+
+    /// 方阵使用的合并规则
+    pub fn merge(&self) -> Merge {
+        self.merge
+    }
+
+    /// 将字符规范化为方阵中的字母：转为大写并应用合并规则，非 ASCII 字母返回 `None`
+    pub fn normalize(&self, c: char) -> Option<u8> {
+        if !c.is_ascii_alphabetic() {
+            return None;
+        }
+        let (kept, dropped) = self.merge.pair();
+        let letter = c.to_ascii_uppercase() as u8;
+        Some(if letter == dropped { kept } else { letter })
+    }
+
+    /// 查找（已规范化的）字母所在的 `(行, 列)`，均从 0 开始
+    pub fn position(&self, letter: u8) -> (usize, usize) {
+        let index = self
+            .cells
+            .iter()
+            .position(|&cell| cell == letter)
+            .expect("normalized letter is always in the square");
+        (index / 5, index % 5)
+    }
+
+    /// 取出第 `row` 行、第 `col` 列的字母（均从 0 开始）
+    pub fn at(&self, row: usize, col: usize) -> u8 {
+        self.cells[row * 5 + col]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square_with_keyword() {
+        let square = Square::new(b"PLAYFAIREXAMPLE", Merge::IJ);
+        assert_eq!(&square.cells[..10], b"PLAYFIREXM");
+        assert_eq!(square.position(b'E'), (1, 2));
+        assert_eq!(square.at(4, 4), b'Z');
+        assert!(!square.cells.contains(&b'J'));
+    }
+
+    #[test]
+    fn test_merge_choice_changes_square() {
+        let square = Square::new(b"", Merge::CK);
+        assert!(square.cells.contains(&b'J'));
+        assert!(!square.cells.contains(&b'K'));
+        assert_eq!(square.normalize('k'), Some(b'C'));
+        assert_eq!(square.normalize('j'), Some(b'J'));
+        assert_eq!(square.normalize('!'), None);
+    }
+
+    #[test]
+    fn test_parse_merge() {
+        assert_eq!("IJ".parse::<Merge>().unwrap(), Merge::IJ);
+        assert_eq!("k/c".parse::<Merge>().unwrap(), Merge::CK);
+        assert_eq!("uv".parse::<Merge>().unwrap(), Merge::UV);
+        assert!(matches!(
+            "AB".parse::<Merge>(),
+            Err(CipherError::InvalidKey(_))
+        ));
+        assert!("I".parse::<Merge>().is_err());
+    }
+}
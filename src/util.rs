@@ -0,0 +1,90 @@
+//! 各算法共用的小工具函数
+
+use crate::CipherError;
+
+/// 校验字母密钥：必须非空且仅含 ASCII 字母
+///
+/// 失败时返回 `CipherError::InvalidKey`；含非法字符时错误信息中包含第一个非法字符及其字符位置（从 0 开始），
+/// 便于用户定位问题。Vigenere、Beaufort、Playfair 等以关键词为密钥的算法共用这一规则。
+pub(crate) fn validate_ascii_alpha_key(key: &str) -> Result<(), CipherError> {
+    if key.is_empty() {
+        return Err(CipherError::InvalidKey("Key cannot be empty".to_string()));
+    }
+
+    match key.chars().enumerate().find(|(_, c)| !c.is_ascii_alphabetic()) {
+        Some((position, c)) => Err(CipherError::InvalidKey(format!(
+            "Key must contain only ASCII letters, found '{}' at position {}",
+            c, position
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// PKCS#7 填充：补 n 个值为 n 的字节，使长度成为 `block_size` 的整数倍（已对齐时补一整块）
+pub(crate) fn pkcs7_pad(mut bytes: Vec<u8>, block_size: u8) -> Vec<u8> {
+    let pad = block_size - (bytes.len() % block_size as usize) as u8;
+    bytes.resize(bytes.len() + pad as usize, pad);
+    bytes
+}
+
+/// 校验并去除 PKCS#7 填充；长度不是块大小的整数倍或填充字节不合法时返回 `None`，
+/// 由调用方转换为带有算法名的错误
+pub(crate) fn pkcs7_unpad(mut bytes: Vec<u8>, block_size: u8) -> Option<Vec<u8>> {
+    if bytes.is_empty() || !bytes.len().is_multiple_of(block_size as usize) {
+        return None;
+    }
+
+    let pad = *bytes.last()?;
+    if pad == 0
+        || pad > block_size
+        || bytes[bytes.len() - pad as usize..]
+            .iter()
+            .any(|&b| b != pad)
+    {
+        return None;
+    }
+
+    bytes.truncate(bytes.len() - pad as usize);
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_ascii_alpha_key_accepts_letters() {
+        assert_eq!(validate_ascii_alpha_key("Lemon"), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_ascii_alpha_key_rejects_empty() {
+        assert_eq!(
+            validate_ascii_alpha_key(""),
+            Err(CipherError::InvalidKey("Key cannot be empty".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_validate_ascii_alpha_key_reports_position() {
+        assert_eq!(
+            validate_ascii_alpha_key("KEY1"),
+            Err(CipherError::InvalidKey(
+                "Key must contain only ASCII letters, found '1' at position 3".to_string()
+            ))
+        );
+        // 位置按字符而非字节计算
+        assert_eq!(
+            validate_ascii_alpha_key("clé"),
+            Err(CipherError::InvalidKey(
+                "Key must contain only ASCII letters, found 'é' at position 2".to_string()
+            ))
+        );
+        assert_eq!(
+            validate_ascii_alpha_key("two words"),
+            Err(CipherError::InvalidKey(
+                "Key must contain only ASCII letters, found ' ' at position 3".to_string()
+            ))
+        );
+    }
+}
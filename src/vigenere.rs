@@ -1,9 +1,18 @@
 // 引入核心 Trait 和错误类型
-use crate::{Cipher, CipherError};
+use crate::{
+    Cipher, CipherError, Inverse, KeyRequirement, Redacted, caesar::PRINTABLE_LEN, ensure_alphabetic,
+    ensure_safe_charset, util::validate_ascii_alpha_key,
+};
+use std::fmt;
 
+#[derive(Clone)]
 pub struct Vigenere {
     key: Vec<u8>, // 存储密钥的字节数组，方便按索引访问
     len: usize,
+    strict: bool, // 严格模式：遇到非字母字符时报错，而非原样保留
+    count_non_letters: bool, // 非字母字符是否也推进密钥位置
+    safe_charset: bool, // 文件名安全模式：数字同样参与变换，只接受 [A-Za-z0-9._-]
+    printable: bool, // 可打印 ASCII 模式：在 95 个可打印字符上变换，密钥保留原始字节
 }
 
 impl Vigenere {
@@ -11,37 +20,179 @@ impl Vigenere {
     ///
     /// # 参数
     ///
-    /// * `key` - 密钥
-    pub fn new(key: &str) -> Self {
-        // 如果密钥为空，直接退出
+    /// * `key` - 密钥，必须非空且仅含 ASCII 字母，否则返回 `CipherError::InvalidKey`
+    pub fn new(key: &str) -> Result<Self, CipherError> {
+        let key_bytes = parse_key(key)?;
+        let len = key_bytes.len();
+
+        Ok(Self { key: key_bytes, len, strict: false, count_non_letters: false, safe_charset: false, printable: false })
+    }
+
+    /// 跳过校验直接创建实例，适用于调用方已经校验过密钥的场景
+    ///
+    /// 对合法密钥，结果与 [`Vigenere::new`] 完全一致。对非法密钥不会产生未定义行为，
+    /// 但结果没有意义：空密钥会在加解密时 panic（对 0 取模），
+    /// 含非字母字符的密钥可能 panic（debug 构建下的整数溢出）或得到错误的输出。
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 密钥，调用方需保证非空且仅含 ASCII 字母
+    pub fn new_unchecked(key: &str) -> Self {
+        let key_bytes = key.to_ascii_uppercase().into_bytes();
+        let len = key_bytes.len();
+
+        Self { key: key_bytes, len, strict: false, count_non_letters: false, safe_charset: false, printable: false }
+    }
+
+    /// 创建一个在全部 95 个可打印 ASCII 字符（空格到 `~`）上变换的 Vigenere 实例
+    ///
+    /// 与 [`Caesar::printable_ascii`] 对应：明文中的字母、数字、标点与空格都会被变换，
+    /// 控制字符与非 ASCII 字符原样保留。密钥字符 `k` 的偏移量为 `k - ' '`（因此空格表示偏移 0），
+    /// 密钥区分大小写。该模式下 `with_safe_charset` 不生效。
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 密钥，必须非空且仅含可打印 ASCII 字符，否则返回 `CipherError::InvalidKey`
+    ///
+    /// [`Caesar::printable_ascii`]: crate::caesar::Caesar::printable_ascii
+    pub fn printable_ascii(key: &str) -> Result<Self, CipherError> {
         if key.is_empty() {
-            panic!("Key cannot be empty");
+            return Err(CipherError::InvalidKey("Key cannot be empty".to_string()));
         }
-
-        // 如果含有非英文字母，直接退出
-        if !key.chars().all(|c| c.is_ascii_alphabetic()) {
-            panic!("Key must contain only ASCII letters");
+        if !key.chars().all(|c| (' '..='~').contains(&c)) {
+            return Err(CipherError::InvalidKey(
+                "Key must contain only printable ASCII characters".to_string(),
+            ));
         }
 
-        let key = key.to_uppercase(); // 将密钥转换为大写，简化加密逻辑
-        let len = key.len();
-        let key_bytes = key.into_bytes(); // 转移所有权，避免悬空引用
+        let mut cipher = Self::new_unchecked(key);
+        cipher.key = key.as_bytes().to_vec();
+        cipher.printable = true;
+        Ok(cipher)
+    }
+
+    /// 可打印 ASCII 模式下的变换（加密与解密共用，`decrypt` 为 `true` 时反向平移）
+    fn shift_printable(&self, text: &str, decrypt: bool) -> String {
+        let mut key_index = 0;
+        text.chars()
+            .map(|c| {
+                if (' '..='~').contains(&c) {
+                    let shift = self.key[key_index % self.len] - b' ';
+                    let shift = if decrypt { PRINTABLE_LEN - shift } else { shift };
+                    key_index += 1;
+                    (b' ' + (c as u8 - b' ' + shift) % PRINTABLE_LEN) as char
+                } else {
+                    if self.count_non_letters {
+                        key_index += 1;
+                    }
+                    c // 控制字符与非 ASCII 字符原样保留
+                }
+            })
+            .collect()
+    }
+
+    /// 设置严格模式：为 `true` 时，输入中出现任何非 ASCII 字母字符都会返回 `CipherError::InvalidInput`
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// 设置非字母字符是否推进密钥位置
+    ///
+    /// 默认为 `false`：只有字母消耗密钥字母，空格、标点等原样保留且不占用密钥位置。
+    /// 为 `true` 时非字母字符同样原样输出，但会使密钥索引前进一位，
+    /// 因此两种策略得到的密文不同，加密与解密两端必须使用同一种策略。
+    pub fn with_count_non_letters(mut self, count_non_letters: bool) -> Self {
+        self.count_non_letters = count_non_letters;
+        self
+    }
+
+    /// 设置文件名安全模式，规则与 [`Caesar::with_safe_charset`] 相同
+    ///
+    /// 数字按当前密钥字母的偏移量 mod 10 在 `0-9` 内循环平移，并与字母一样消耗密钥位置。
+    ///
+    /// [`Caesar::with_safe_charset`]: crate::caesar::Caesar::with_safe_charset
+    pub fn with_safe_charset(mut self, safe_charset: bool) -> Self {
+        self.safe_charset = safe_charset;
+        self
+    }
+}
+
+/// 生成 26×26 的 Vigenere 方阵 (tabula recta)，常用于教学演示
+///
+/// 第 N 行（从 0 开始）对应密钥字母 `A + N`，即把字母表循环左移 N 位；
+/// 同一行内字母以空格分隔，行与行之间以 `\n` 分隔（末尾无换行）。
+/// 加密时在"密钥字母"所在行、"明文字母"所在列找到的就是密文字母。
+///
+/// # 示例
+///
+/// ```
+/// use ciphery::vigenere::tabula_recta;
+/// let table = tabula_recta();
+/// assert!(table.lines().nth(1).unwrap().starts_with("B C D"));
+/// ```
+pub fn tabula_recta() -> String {
+    (0..26u8)
+        .map(|row| {
+            (0..26u8)
+                .map(|col| ((row + col) % 26 + b'A') as char)
+                .map(String::from)
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
 
-        Self { key: key_bytes, len }
+impl fmt::Debug for Vigenere {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Vigenere")
+            .field("key", &Redacted { len: Some(self.len) })
+            .field("strict", &self.strict)
+            .field("count_non_letters", &self.count_non_letters)
+            .field("safe_charset", &self.safe_charset)
+            .field("printable", &self.printable)
+            .finish()
     }
 }
 
+/// 解析字母密钥：校验后统一转换为大写字节数组
+///
+/// Vigenere 及其变体（如 Beaufort）以及 Playfair 共用这套密钥规则，校验见 [`validate_ascii_alpha_key`]。
+pub(crate) fn parse_key(key: &str) -> Result<Vec<u8>, CipherError> {
+    validate_ascii_alpha_key(key)?;
+
+    // 将密钥转换为大写，简化加密逻辑；into_bytes 转移所有权，避免悬空引用
+    Ok(key.to_uppercase().into_bytes())
+}
+
 impl Cipher for Vigenere {
     fn encrypt(&self, text: &str) -> Result<String, CipherError> {
+        if self.strict {
+            ensure_alphabetic(text)?;
+        }
+        if self.printable {
+            return Ok(self.shift_printable(text, false));
+        }
+        if self.safe_charset {
+            ensure_safe_charset(text)?;
+        }
         let mut key_index = 0;
         Ok(text.chars().map(|c| {
             if c.is_ascii_alphabetic() {
                 let base = if c.is_ascii_uppercase() { b'A' } else { b'a' };
                 let key_char = self.key[key_index % self.len];
-                let shift = (key_char - b'A') as u8; // 计算当前密钥字符的偏移量
+                let shift = key_char - b'A'; // 计算当前密钥字符的偏移量
                 key_index += 1; // 只有当遇到字母时才增加密钥索引
                 ((c as u8 - base + shift) % 26 + base) as char
+            } else if self.safe_charset && c.is_ascii_digit() {
+                let shift = (self.key[key_index % self.len] - b'A') % 10;
+                key_index += 1;
+                ((c as u8 - b'0' + shift) % 10 + b'0') as char
             } else {
+                if self.count_non_letters {
+                    key_index += 1;
+                }
                 c // 非字母字符直接返回，不加密
             }
         })
@@ -52,15 +203,31 @@ impl Cipher for Vigenere {
     }
 
     fn decrypt(&self, text: &str) -> Result<String, CipherError> {
+        if self.strict {
+            ensure_alphabetic(text)?;
+        }
+        if self.printable {
+            return Ok(self.shift_printable(text, true));
+        }
+        if self.safe_charset {
+            ensure_safe_charset(text)?;
+        }
         let mut key_index = 0;
         Ok(text.chars().map(|c| {
             if c.is_ascii_alphabetic() {
                 let base = if c.is_ascii_uppercase() { b'A' } else { b'a' };
                 let key_char = self.key[key_index % self.len];
-                let shift = (key_char - b'A') as u8; // 计算当前密钥字符的偏移量
+                let shift = key_char - b'A'; // 计算当前密钥字符的偏移量
                 key_index += 1; // 只有当遇到字母时才增加密钥索引
                 ((c as u8 - base + 26 - shift) % 26 + base) as char
+            } else if self.safe_charset && c.is_ascii_digit() {
+                let shift = (self.key[key_index % self.len] - b'A') % 10;
+                key_index += 1;
+                ((c as u8 - b'0' + 10 - shift) % 10 + b'0') as char
             } else {
+                if self.count_non_letters {
+                    key_index += 1;
+                }
                 c // 非字母字符直接返回，不加密
             }
         })
@@ -68,6 +235,24 @@ impl Cipher for Vigenere {
         )
         // Vigenre 算法不会出错，直接 Ok 返回加密结果
     }
+
+    fn inverse(&self) -> Box<dyn Cipher> {
+        Box::new(Inverse(self.clone()))
+    }
+
+    fn key_requirement(&self) -> KeyRequirement {
+        KeyRequirement::Keyword
+    }
+
+    fn rekey(&self, new_key: &str) -> Result<Box<dyn Cipher>, CipherError> {
+        // 按当前模式校验新密钥，其余设置沿用
+        let fresh = if self.printable {
+            Self::printable_ascii(new_key)?
+        } else {
+            Self::new(new_key)?
+        };
+        Ok(Box::new(Self { key: fresh.key, len: fresh.len, ..self.clone() }))
+    }
 }
 
 #[cfg(test)]
@@ -78,7 +263,7 @@ mod tests {
     fn test_vigenere_encrypt() {
         let text = "ATTACK AT DAWN!";
         let key = "LEMON";
-        let cipher = Vigenere::new(key);
+        let cipher = Vigenere::new(key).unwrap();
         let encrypted = cipher.encrypt(text).unwrap();
         assert_eq!(encrypted, "LXFOPV EF RNHR!"); // 注意：空格和感叹号完美保留
     }
@@ -87,7 +272,7 @@ mod tests {
     fn test_vigenere_decrypt() {
         let text = "LXFOPV EF RNHR!";
         let key = "LEMON";
-        let cipher = Vigenere::new(key);
+        let cipher = Vigenere::new(key).unwrap();
         let decrypted = cipher.decrypt(text).unwrap();
         assert_eq!(decrypted, "ATTACK AT DAWN!");
     }
@@ -96,9 +281,140 @@ mod tests {
     fn test_vigenere_with_unicode() {
         let text = "Hello 世界";
         let key = "KEY";
-        let cipher = Vigenere::new(key);
+        let cipher = Vigenere::new(key).unwrap();
         let encrypted = cipher.encrypt(text).unwrap();
         assert_eq!(encrypted, "Rijvs 世界");
         assert_eq!(cipher.decrypt(&encrypted).unwrap(), text);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_vigenere_inverse() {
+        let cipher = Vigenere::new("LEMON").unwrap();
+        let inverse = cipher.inverse();
+        let text = "LXFOPV EF RNHR!";
+        assert_eq!(
+            inverse.encrypt(text).unwrap(),
+            cipher.decrypt(text).unwrap()
+        );
+        assert_eq!(inverse.inverse().encrypt("ATTACK AT DAWN!").unwrap(), text);
+    }
+
+    #[test]
+    fn test_vigenere_strict_mode() {
+        let strict = Vigenere::new("LEMON").unwrap().with_strict(true);
+        assert!(matches!(strict.encrypt("ATTACK AT DAWN"), Err(CipherError::InvalidInput(_))));
+        assert_eq!(strict.encrypt("ATTACKATDAWN").unwrap(), "LXFOPVEFRNHR");
+        assert_eq!(strict.decrypt("LXFOPVEFRNHR").unwrap(), "ATTACKATDAWN");
+    }
+
+    #[test]
+    fn test_tabula_recta() {
+        let table = tabula_recta();
+        let rows: Vec<&str> = table.lines().collect();
+        assert_eq!(rows.len(), 26);
+        assert!(rows[0].starts_with("A B C D"));
+        assert!(rows[1].starts_with("B C D E"));
+        assert!(rows[25].starts_with("Z A B C"));
+        assert!(rows.iter().all(|row| row.len() == 51));
+
+        // 方阵与加密结果一致：密钥 L 行、明文 A 列即密文 L
+        let cipher = Vigenere::new("L").unwrap();
+        assert_eq!(rows[11].split(' ').next().unwrap(), cipher.encrypt("A").unwrap());
+    }
+
+    #[test]
+    fn test_vigenere_debug_redacts_key() {
+        let debug = format!("{:?}", Vigenere::new("lemon").unwrap());
+        assert_eq!(
+            debug,
+            "Vigenere { key: <redacted, len=5>, strict: false, count_non_letters: false, safe_charset: false, printable: false }"
+        );
+        assert!(!debug.to_uppercase().contains("LEMON"));
+    }
+
+    #[test]
+    fn test_vigenere_count_non_letters_policies() {
+        let skip = Vigenere::new("KEY").unwrap();
+        let count = Vigenere::new("KEY").unwrap().with_count_non_letters(true);
+
+        // 默认策略下空格不占用密钥位置：C 使用 Y；计数策略下空格占用了 Y，C 改用 K
+        let skipped = skip.encrypt("AB CD").unwrap();
+        let counted = count.encrypt("AB CD").unwrap();
+        assert_eq!(skipped, "KF AN");
+        assert_eq!(counted, "KF MH");
+
+        assert_eq!(skip.decrypt(&skipped).unwrap(), "AB CD");
+        assert_eq!(count.decrypt(&counted).unwrap(), "AB CD");
+    }
+
+    #[test]
+    fn test_vigenere_safe_charset_keeps_filename_legal() {
+        let cipher = Vigenere::new("KEY").unwrap().with_safe_charset(true);
+        let encrypted = cipher.encrypt("report_2024.txt").unwrap();
+        // 字母与数字按 K(10) E(4) Y(24) 循环变换，数字取偏移量 mod 10：0、4、4
+        assert_eq!(encrypted, "binyvr_2464.xvd");
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), "report_2024.txt");
+        assert!(matches!(
+            cipher.encrypt("a b"),
+            Err(CipherError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_vigenere_printable_ascii_shifts_everything() {
+        let cipher = Vigenere::printable_ascii("k3Y").unwrap();
+        let encrypted = cipher.encrypt("Pass@123").unwrap();
+        assert_eq!(encrypted.len(), 8);
+        assert!(encrypted.chars().zip("Pass@123".chars()).all(|(e, p)| e != p));
+        assert!(encrypted.chars().all(|c| (' '..='~').contains(&c)));
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), "Pass@123");
+
+        // 控制字符与非 ASCII 字符原样保留
+        let mixed = "a\tb 世";
+        let encrypted = cipher.encrypt(mixed).unwrap();
+        assert!(encrypted.contains('\t') && encrypted.ends_with('世'));
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), mixed);
+
+        assert!(matches!(
+            Vigenere::printable_ascii("ключ"),
+            Err(CipherError::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_vigenere_rekey() {
+        let text = "Attack at dawn!";
+        let original = Vigenere::new("LEMON").unwrap().with_count_non_letters(true);
+        let rekeyed = original.rekey("KEY").unwrap();
+
+        let encrypted = rekeyed.encrypt(text).unwrap();
+        assert_ne!(encrypted, original.encrypt(text).unwrap());
+        assert_eq!(rekeyed.decrypt(&encrypted).unwrap(), text);
+        // 其余设置保持不变
+        let expected = Vigenere::new("KEY").unwrap().with_count_non_letters(true);
+        assert_eq!(encrypted, expected.encrypt(text).unwrap());
+
+        assert!(matches!(original.rekey("K3Y"), Err(CipherError::InvalidKey(_))));
+    }
+
+    #[test]
+    fn test_vigenere_rejects_invalid_key() {
+        assert!(matches!(Vigenere::new(""), Err(CipherError::InvalidKey(_))));
+        assert!(matches!(
+            Vigenere::new("KEY1"),
+            Err(CipherError::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_vigenere_unchecked_matches_checked() {
+        let text = "Attack at dawn! 世界";
+        for key in ["LEMON", "lemon", "KeY", "A"] {
+            let checked = Vigenere::new(key).unwrap();
+            let unchecked = Vigenere::new_unchecked(key);
+            let encrypted = checked.encrypt(text).unwrap();
+            assert_eq!(unchecked.encrypt(text).unwrap(), encrypted);
+            assert_eq!(unchecked.decrypt(&encrypted).unwrap(), text);
+        }
+    }
+}
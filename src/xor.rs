@@ -1,8 +1,29 @@
 // 引入核心 Trait 和错误类型
-use crate::{Cipher, CipherError};
+use crate::util::{pkcs7_pad, pkcs7_unpad};
+use crate::{Cipher, CipherError, Inverse, KeyRequirement, Redacted};
+use std::fmt;
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
 
+/// XOR 密文的文本编码方式
+///
+/// XOR 的结果是任意字节，无法直接作为字符串展示，因此需要编码为可打印文本。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// 十六进制编码（默认）
+    Hex,
+    /// 标准 Base64 编码（带 `=` 填充）
+    Base64,
+}
+
+#[derive(Clone)]
 pub struct Xor {
   key: Vec<u8>,
+  // 为 None 时：加密输出 hex，解密时自动识别 hex / base64
+  encoding: Option<Encoding>,
+  // 为 Some 时：加密前按 PKCS#7 填充到块大小的整数倍，解密后校验并去除填充
+  block_size: Option<u8>,
+  // 为 true 时：加密前在明文字节前附加 CRC-32 校验和，解密后校验
+  checksum: bool,
 }
 
 impl Xor {
@@ -10,44 +31,225 @@ impl Xor {
     ///
     /// # 参数
     ///
-    /// * `key` - 密钥
-    pub fn new(key: &str) -> Self {
+    /// * `key` - 密钥，为空时返回 `CipherError::InvalidKey`
+    pub fn new(key: &str) -> Result<Self, CipherError> {
         if key.is_empty() {
-            panic!("Key cannot be empty");
+            return Err(CipherError::InvalidKey("XOR key cannot be empty".to_string()));
         }
-        Xor { key: key.bytes().collect() }
+        Ok(Self::new_unchecked(key))
+    }
+
+    /// 跳过校验直接创建实例，适用于调用方已经校验过密钥的场景
+    ///
+    /// 对非空密钥，结果与 [`Xor::new`] 完全一致。传入空密钥不会 panic 也不会产生未定义行为，
+    /// 但结果是错误的：没有密钥字节可供异或，加密输出为空。
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 密钥，调用方需保证非空
+    pub fn new_unchecked(key: &str) -> Self {
+        Xor { key: key.bytes().collect(), encoding: None, block_size: None, checksum: false }
         // key.bytes() 返回一个迭代器，我们使用 collect() 将其转换为 Vec<u8>，方便后续按索引访问
     }
-}
 
-impl Cipher for Xor {
-    fn encrypt(&self, text: &str) -> Result<String, CipherError> {
-        let xored_bytes: Vec<u8> = text.bytes()
-            .zip(self.key.iter().cycle())
-            .map(|(text_byte, key_byte)| {text_byte^key_byte})
-            .collect();
+    /// 显式指定密文编码，加密与解密都将只使用该编码（关闭解密时的自动识别）
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = Some(encoding);
+        self
+    }
 
-        // hex::encode 返回类型是 String
-        Ok(hex::encode(xored_bytes))
-        
+    /// 启用 PKCS#7 填充，隐藏短消息的真实长度
+    ///
+    /// 加密前将明文字节填充到 `block_size` 的整数倍（即使已经对齐也会补一整块），
+    /// 这样密文长度只暴露"块数"而不暴露精确的消息长度。解密时会校验并去除填充，
+    /// 因此加密与解密两端必须使用相同的块大小。
+    ///
+    /// # 参数
+    ///
+    /// * `block_size` - 块大小（字节），取值 1..=255，为 0 时返回 `CipherError::InvalidKey`
+    pub fn with_block_size(mut self, block_size: u8) -> Result<Self, CipherError> {
+        if block_size == 0 {
+            return Err(CipherError::InvalidKey(
+                "XOR block size must be between 1 and 255".to_string(),
+            ));
+        }
+        self.block_size = Some(block_size);
+        Ok(self)
     }
 
-    fn decrypt(&self, hex_text: &str) -> Result<String, CipherError> {
-        let text_bytes = hex::decode(hex_text)
-            .map_err(|e| CipherError::HexCodingError(format!("XOR decryption failed: {}", e)))?;
-        // hex::decode 可能失败，所以我们使用 map_err 将错误转换为 CipherError::HexCodingError
-        // hex::decode 返回类型是 Result<Vec<u8>, hex::FromHexError>，我们需要处理这个错误
+    /// 启用完整性校验：加密前在明文字节前附加 4 字节的 CRC-32 校验和（大端序），与明文一起异或
+    ///
+    /// 使用错误的密钥解密 XOR 密文通常依然会得到"看起来合理"的字节，难以察觉；
+    /// 启用后，解密时会重新计算校验和，与还原出的校验和不一致时返回
+    /// `CipherError::InvalidInput("integrity check failed")`。
+    /// 校验和本身也被加密，不会泄露明文的 CRC；它只能发现意外的错误（如密钥错误），不能防止有意篡改。
+    /// 加密与解密两端必须同时启用。
+    pub fn with_checksum(mut self) -> Self {
+        self.checksum = true;
+        self
+    }
 
-        let decrypted_bytes: Vec<u8> = text_bytes.iter()
+    /// 将字节与循环使用的密钥逐字节异或
+    fn xor_bytes(&self, bytes: &[u8]) -> Vec<u8> {
+        bytes
+            .iter()
             .zip(self.key.iter().cycle())
             .map(|(text_byte, key_byte)| text_byte ^ key_byte)
-            .collect();
+            .collect()
+    }
+
+    /// 加密并直接返回原始字节形式的密文（不做 hex / base64 编码），启用了填充时包含 PKCS#7 填充
+    ///
+    /// 异或作用在 UTF-8 字节上而不是字符上，因此像 `"世界🦀"` 这样的多字节字符会被逐字节打散，
+    /// 结果通常不是合法的 UTF-8，不能转换成 `String`，只能作为字节写入文件，再交给 [`Xor::decrypt_bytes`] 还原。
+    pub fn encrypt_bytes(&self, text: &str) -> Vec<u8> {
+        let mut plain_bytes = Vec::with_capacity(text.len() + 4);
+        if self.checksum {
+            plain_bytes.extend_from_slice(&crc32(text.as_bytes()).to_be_bytes());
+        }
+        plain_bytes.extend_from_slice(text.as_bytes());
+        if let Some(block_size) = self.block_size {
+            plain_bytes = pkcs7_pad(plain_bytes, block_size);
+        }
+        self.xor_bytes(&plain_bytes)
+    }
+
+    /// 直接解密原始字节形式的密文（未经 hex / base64 编码，例如直接写入文件的异或结果）
+    ///
+    /// 启用了填充时同样会校验并去除 PKCS#7 填充，启用了校验和时会校验 CRC-32；解密结果不是合法 UTF-8 时返回错误。
+    /// UTF-8 校验在全部字节异或、去除填充之后才进行，因此多字节字符即使在密文中被拆散也能完整还原；
+    /// 调用方也应先读入完整的密文再解密，而不要按任意字节边界分段解密。
+    pub fn decrypt_bytes(&self, bytes: &[u8]) -> Result<String, CipherError> {
+        let mut decrypted_bytes = self.xor_bytes(bytes);
+        if let Some(block_size) = self.block_size {
+            decrypted_bytes = pkcs7_unpad(decrypted_bytes, block_size).ok_or_else(|| {
+                CipherError::InvalidInput("XOR decryption failed: invalid padding".to_string())
+            })?;
+        }
+        if self.checksum {
+            decrypted_bytes = verify_checksum(decrypted_bytes)?;
+        }
 
         match String::from_utf8(decrypted_bytes) {
             Ok(res) => Ok(res),
             Err(e) => Err(CipherError::HexCodingError(format!("XOR decryption failed: {}", e))),
         }
     }
+
+    /// 将密文文本解码为字节
+    ///
+    /// 指定了编码时严格按该编码解析；否则自动识别：
+    /// 先尝试 hex（偶数长度、仅含 `0-9a-fA-F`），失败后再尝试 Base64（长度为 4 的倍数、
+    /// 仅含 `A-Za-z0-9+/` 及末尾 `=` 填充）。
+    ///
+    /// 注意这个启发式存在歧义：像 `"deadbeef"` 这样的字符串同时是合法的 hex 与 Base64，
+    /// 此时总是按 hex 解析（hex 是默认的加密输出格式）。若密文确实是 Base64，
+    /// 请使用 [`Xor::with_encoding`] 显式指定。
+    ///
+    /// 解码前会去掉首尾的 ASCII 空白（例如文件末尾的换行符），这类空白不属于密文本身；
+    /// 中间出现的空白或奇数长度等真正的格式错误依然会报错。
+    fn decode(&self, text: &str) -> Result<Vec<u8>, CipherError> {
+        let text = text.trim_ascii();
+        match self.encoding {
+            Some(Encoding::Hex) => hex::decode(text)
+                .map_err(|e| CipherError::HexCodingError(format!("XOR decryption failed: {}", e))),
+            Some(Encoding::Base64) => BASE64
+                .decode(text)
+                .map_err(|e| CipherError::InvalidInput(format!("XOR decryption failed: {}", e))),
+            None => hex::decode(text).or_else(|hex_error| {
+                BASE64.decode(text).map_err(|_| {
+                    CipherError::InvalidInput(format!(
+                        "XOR decryption failed: input is neither valid hex ({}) nor base64",
+                        hex_error
+                    ))
+                })
+            }),
+        }
+    }
+}
+
+impl fmt::Debug for Xor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Xor")
+            .field("key", &Redacted { len: Some(self.key.len()) })
+            .field("encoding", &self.encoding)
+            .field("block_size", &self.block_size)
+            .field("checksum", &self.checksum)
+            .finish()
+    }
+}
+
+/// CRC-32（IEEE 802.3，即 zlib / PNG 使用的版本），逐位计算，不依赖查找表
+///
+/// 只用于发现"密钥错误"这类意外损坏，不能抵御有意的篡改。
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// 拆出开头的 4 字节 CRC-32 并与其余字节重新计算的结果比较，一致时返回去掉校验和后的字节
+fn verify_checksum(mut bytes: Vec<u8>) -> Result<Vec<u8>, CipherError> {
+    let integrity_error = || CipherError::InvalidInput("integrity check failed".to_string());
+    if bytes.len() < 4 {
+        return Err(integrity_error());
+    }
+    let text = bytes.split_off(4);
+    let expected = u32::from_be_bytes(bytes.try_into().map_err(|_| integrity_error())?);
+    if crc32(&text) == expected {
+        Ok(text)
+    } else {
+        Err(integrity_error())
+    }
+}
+
+impl Cipher for Xor {
+    fn encrypt(&self, text: &str) -> Result<String, CipherError> {
+        let xored_bytes = self.encrypt_bytes(text);
+
+        // hex::encode / BASE64.encode 返回类型都是 String
+        match self.encoding.unwrap_or(Encoding::Hex) {
+            Encoding::Hex => Ok(hex::encode(xored_bytes)),
+            Encoding::Base64 => Ok(BASE64.encode(xored_bytes)),
+        }
+    }
+
+    fn decrypt(&self, encoded_text: &str) -> Result<String, CipherError> {
+        // 解码可能失败（既不是 hex 也不是 base64），decode 内部已转换为 CipherError
+        let text_bytes = self.decode(encoded_text)?;
+        self.decrypt_bytes(&text_bytes)
+    }
+
+    fn inverse(&self) -> Box<dyn Cipher> {
+        // 加密输出的是 hex 而非原始字节，因此 Xor 并不是"自逆"的，需要包装
+        Box::new(Inverse(self.clone()))
+    }
+
+    fn key_requirement(&self) -> KeyRequirement {
+        KeyRequirement::Bytes
+    }
+
+    fn rekey(&self, new_key: &str) -> Result<Box<dyn Cipher>, CipherError> {
+        // 编码、填充与校验和设置沿用
+        Ok(Box::new(Self { key: Self::new(new_key)?.key, ..self.clone() }))
+    }
+
+    fn is_reciprocal(&self) -> bool {
+        // 原始字节上的异或是自反的，但加密输出经过了 hex / base64 编码，
+        // 对密文再次"加密"只会得到编码后的编码，因此这里返回 false
+        false
+    }
+
+    fn output_is_binary_safe(&self) -> bool {
+        // 加密输出为 hex / base64 编码
+        true
+    }
     
 }
 
@@ -55,11 +257,43 @@ impl Cipher for Xor {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_crc32_known_values() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b"The quick brown fox jumps over the lazy dog"), 0x414F_A339);
+    }
+
+    #[test]
+    fn test_xor_checksum_detects_wrong_key() {
+        let text = "Attack at dawn, 世界!";
+        let cipher = Xor::new("secret").unwrap().with_checksum();
+        let encrypted = cipher.encrypt(text).unwrap();
+        // 多出 4 字节校验和
+        assert_eq!(encrypted.len(), (text.len() + 4) * 2);
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), text);
+
+        // 错误的密钥：不启用校验和时可能得到"合理"的结果，启用后几乎总会报错
+        // （CRC-32 恰好碰撞的概率约为 2^-32）
+        let integrity_error = Err(CipherError::InvalidInput("integrity check failed".to_string()));
+        let wrong_key = Xor::new("secreT").unwrap().with_checksum();
+        assert_eq!(wrong_key.decrypt(&encrypted), integrity_error);
+        assert_eq!(cipher.decrypt("00"), integrity_error);
+
+        // 与填充组合使用
+        let padded = Xor::new("secret").unwrap().with_checksum().with_block_size(16).unwrap();
+        let encrypted = padded.encrypt(text).unwrap();
+        assert_eq!(padded.decrypt(&encrypted).unwrap(), text);
+        // 密钥错误时通常先在填充校验处失败
+        let wrong_key = Xor::new("other").unwrap().with_checksum().with_block_size(16).unwrap();
+        assert!(wrong_key.decrypt(&encrypted).is_err());
+    }
+
     #[test]
     fn test_xor_encrypt_decrypt() {
         let text = "Hello 🦀 (Rust) 世界!"; // 包含英文、Emoji、符号、中文
         let key = "super_secret_key_123";
-        let cipher = Xor::new(key);
+        let cipher = Xor::new(key).unwrap();
         // 1. 测试加密
         let encrypted_hex = cipher.encrypt(text).unwrap();
         println!("加密后的 Hex: {}", encrypted_hex);
@@ -72,11 +306,162 @@ mod tests {
         assert_eq!(decrypted_text, text);
     }
 
+    #[test]
+    fn test_xor_padded_output_lengths() {
+        let cipher = Xor::new("key").unwrap().with_block_size(16).unwrap();
+        // (明文长度, 填充后的字节数)：已对齐时也会补一整块
+        for (len, padded) in [(0, 16), (1, 16), (15, 16), (16, 32), (17, 32), (40, 48)] {
+            let text = "a".repeat(len);
+            let encrypted = cipher.encrypt(&text).unwrap();
+            assert_eq!(encrypted.len(), padded * 2); // hex 每字节两个字符
+            assert_eq!(cipher.decrypt(&encrypted).unwrap(), text);
+        }
+    }
+
+    #[test]
+    fn test_xor_bad_padding_is_rejected() {
+        // 未填充的密文交给启用填充的实例解密，填充校验必然失败
+        let unpadded = Xor::new("key").unwrap().encrypt("hello").unwrap();
+        let result = Xor::new("key").unwrap().with_block_size(8).unwrap().decrypt(&unpadded);
+        assert_eq!(
+            result,
+            Err(CipherError::InvalidInput("XOR decryption failed: invalid padding".to_string()))
+        );
+        assert!(Xor::new("key").unwrap().with_block_size(0).is_err());
+    }
+
     #[test]
     fn test_xor_invalid_hex_decrypt() {
         // 测试用户瞎输解密内容的情况
         let invalid_hex = "this is not hex";
-        let result = Xor::new("key").decrypt(invalid_hex);
+        let result = Xor::new("key").unwrap().decrypt(invalid_hex);
         assert!(result.is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_xor_tolerates_surrounding_whitespace() {
+        let cipher = Xor::new("key").unwrap();
+        let encrypted = cipher.encrypt("Hello").unwrap();
+        assert_eq!(cipher.decrypt(&format!("{}\n", encrypted)).unwrap(), "Hello");
+        assert_eq!(cipher.decrypt(&format!("  {}\r\n", encrypted)).unwrap(), "Hello");
+
+        // 密钥为单个 0 字节时异或是恒等变换，密文即明文的 hex
+        let identity = Xor::new("\0").unwrap();
+        assert_eq!(identity.decrypt("48656c6c6f\n").unwrap(), "Hello");
+    }
+
+    #[test]
+    fn test_xor_odd_length_hex_still_errors() {
+        let result = Xor::new("key").unwrap().decrypt("48656c6c6\n");
+        assert_eq!(
+            result,
+            Err(CipherError::InvalidInput(
+                "XOR decryption failed: input is neither valid hex (Odd number of digits) nor base64"
+                    .to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_xor_base64_encrypt_decrypt() {
+        let text = "Hello 世界!";
+        let cipher = Xor::new("key").unwrap().with_encoding(Encoding::Base64);
+        let encrypted = cipher.encrypt(text).unwrap();
+        assert_eq!(encrypted, BASE64.encode(Xor::new("key").unwrap().xor_bytes(text.as_bytes())));
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), text);
+    }
+
+    #[test]
+    fn test_xor_auto_detect_encoding() {
+        let text = "Attack at dawn!";
+        let auto = Xor::new("key").unwrap();
+
+        // 明显是 hex 的密文
+        let hex_cipher = Xor::new("key").unwrap().with_encoding(Encoding::Hex).encrypt(text).unwrap();
+        assert!(hex_cipher.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(auto.decrypt(&hex_cipher).unwrap(), text);
+
+        // 明显是 base64 的密文（含非 hex 字符与填充）
+        let b64_cipher = Xor::new("key").unwrap().with_encoding(Encoding::Base64).encrypt(text).unwrap();
+        assert!(!b64_cipher.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(auto.decrypt(&b64_cipher).unwrap(), text);
+    }
+
+    #[test]
+    fn test_xor_explicit_encoding_disables_detection() {
+        let b64_cipher = Xor::new("key").unwrap().with_encoding(Encoding::Base64).encrypt("hi there").unwrap();
+        let result = Xor::new("key").unwrap().with_encoding(Encoding::Hex).decrypt(&b64_cipher);
+        assert!(matches!(result, Err(CipherError::HexCodingError(_))));
+    }
+
+    #[test]
+    fn test_xor_debug_redacts_key() {
+        let debug = format!("{:?}", Xor::new("hunter2").unwrap());
+        assert!(debug.contains("redacted"));
+        assert!(debug.contains("len=7"));
+        assert!(!debug.contains("hunter2"));
+        // 也不能以字节数组的形式泄露
+        assert!(!debug.contains("104, 117"));
+    }
+
+    #[test]
+    fn test_xor_unchecked_matches_checked() {
+        let text = "Hello 🦀 世界!";
+        let checked = Xor::new("secret").unwrap();
+        let unchecked = Xor::new_unchecked("secret");
+        let encrypted = checked.encrypt(text).unwrap();
+        assert_eq!(unchecked.encrypt(text).unwrap(), encrypted);
+        assert_eq!(unchecked.decrypt(&encrypted).unwrap(), text);
+        assert!(matches!(Xor::new(""), Err(CipherError::InvalidKey(_))));
+    }
+
+    #[test]
+    fn test_xor_decrypt_raw_bytes() {
+        let cipher = Xor::new("key").unwrap();
+        let raw = hex::decode(cipher.encrypt("Hello 世界").unwrap()).unwrap();
+        assert_eq!(cipher.decrypt_bytes(&raw).unwrap(), "Hello 世界");
+
+        let padded = Xor::new("key").unwrap().with_block_size(8).unwrap();
+        let raw = hex::decode(padded.encrypt("hi").unwrap()).unwrap();
+        assert_eq!(padded.decrypt_bytes(&raw).unwrap(), "hi");
+    }
+
+    #[test]
+    fn test_xor_unicode_roundtrip_matrix() {
+        let text = "世界🦀 Hello";
+        for key in ["key", "ключ"] {
+            for block_size in [None, Some(1), Some(7), Some(16)] {
+                let mut base = Xor::new(key).unwrap();
+                if let Some(block_size) = block_size {
+                    base = base.with_block_size(block_size).unwrap();
+                }
+                for encoding in [None, Some(Encoding::Hex), Some(Encoding::Base64)] {
+                    let cipher = match encoding {
+                        Some(encoding) => base.clone().with_encoding(encoding),
+                        None => base.clone(),
+                    };
+                    let encrypted = cipher.encrypt(text).unwrap();
+                    assert_eq!(cipher.decrypt(&encrypted).unwrap(), text, "{} {:?} {:?}", key, block_size, encoding);
+                }
+
+                // 原始字节输出与编码输出是同一份字节
+                let raw = base.encrypt_bytes(text);
+                assert_eq!(hex::encode(&raw), base.encrypt(text).unwrap());
+                assert_eq!(base.decrypt_bytes(&raw).unwrap(), text, "{} {:?} raw", key, block_size);
+            }
+        }
+    }
+
+    #[test]
+    fn test_xor_raw_unicode_needs_whole_ciphertext() {
+        let cipher = Xor::new("k").unwrap();
+        let raw = cipher.encrypt_bytes("世界🦀");
+        // 多字节字符被逐字节打散，密文本身不是合法的 UTF-8
+        assert!(String::from_utf8(raw.clone()).is_err());
+
+        // 在字符中间截断后单独解密会失败……
+        assert!(matches!(cipher.decrypt_bytes(&raw[..4]), Err(CipherError::HexCodingError(_))));
+        // ……而完整的字节序列能原样还原
+        assert_eq!(cipher.decrypt_bytes(&raw).unwrap(), "世界🦀");
+    }
+}
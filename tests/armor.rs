@@ -0,0 +1,49 @@
+//! 装甲输出（`--armor`）的集成测试：直接运行编译好的二进制程序
+
+use ciphery::armor::{BEGIN, END};
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn test_armored_caesar_roundtrip_reads_algorithm_from_header() {
+    let output = Command::new(env!("CARGO_BIN_EXE_ciphery-bin"))
+        .args([
+            "encrypt",
+            "-a",
+            "caesar",
+            "-k",
+            "3",
+            "--armor",
+            "-t",
+            "Hello, World!",
+        ])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let start = stdout.find(BEGIN).expect("armored block in output");
+    let end = stdout.find(END).expect("armored block in output") + END.len();
+    let block = &stdout[start..end];
+    assert!(
+        block.contains("algo: caesar\n\nKhoor, Zruog!\n"),
+        "{}",
+        block
+    );
+
+    // 解密时故意指定另一种算法，应以头部记录的 caesar 为准
+    // （只检查 stdout 中的结果：启用 `log` feature 时提示信息会改为输出到 stderr）
+    let path = std::env::temp_dir().join(format!("ciphery-armor-{}.txt", std::process::id()));
+    fs::write(&path, block).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_ciphery-bin"))
+        .args(["decrypt", "-a", "vigenere", "-k", "3", "-f"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    fs::remove_file(&path).unwrap();
+
+    assert!(
+        stdout.contains("[result] Decrypted text:\nHello, World!"),
+        "{}",
+        stdout
+    );
+}
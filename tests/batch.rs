@@ -0,0 +1,68 @@
+//! 批量处理多个文件的集成测试：直接运行编译好的二进制程序
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// 在系统临时目录下创建本测试专用的目录
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ciphery-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_batch_encrypt_two_files() {
+    let dir = temp_dir("batch");
+    let first = dir.join("first.txt");
+    let second = dir.join("second.txt");
+    fs::write(&first, "hello").unwrap();
+    fs::write(&second, "Attack at dawn!").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_ciphery-bin"))
+        .args(["encrypt", "-a", "caesar", "-k", "3", "-f"])
+        .arg(&first)
+        .arg("-f")
+        .arg(&second)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(
+        fs::read_to_string(dir.join("first.txt.enc")).unwrap(),
+        "khoor"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("second.txt.enc")).unwrap(),
+        "Dwwdfn dw gdzq!"
+    );
+    assert!(stdout.contains("[summary] 2 succeeded, 0 failed"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_batch_continues_past_failures() {
+    let dir = temp_dir("batch-failure");
+    let good = dir.join("good.txt");
+    let missing = dir.join("missing.txt");
+    fs::write(&good, "hello").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_ciphery-bin"))
+        .args(["encrypt", "-a", "caesar", "-k", "3", "-f"])
+        .arg(&missing)
+        .arg("-f")
+        .arg(&good)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(
+        fs::read_to_string(dir.join("good.txt.enc")).unwrap(),
+        "khoor"
+    );
+    assert!(stdout.contains("[failed]"));
+    assert!(stdout.contains("[summary] 1 succeeded, 1 failed"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
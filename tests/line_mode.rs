@@ -0,0 +1,44 @@
+//! 逐行解密（`--lines`）的集成测试：直接运行编译好的二进制程序
+
+use ciphery::{Cipher, xor::Xor};
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn test_continue_on_error_skips_corrupt_line() {
+    let cipher = Xor::new("key").unwrap();
+    let path = std::env::temp_dir().join(format!("ciphery-lines-{}.txt", std::process::id()));
+    let content = format!(
+        "{}\nnot-valid-hex!\n{}\n",
+        cipher.encrypt("first record").unwrap(),
+        cipher.encrypt("third record").unwrap()
+    );
+    fs::write(&path, content).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_ciphery-bin"))
+        .args([
+            "decrypt",
+            "-a",
+            "xor",
+            "-k",
+            "key",
+            "--lines",
+            "--continue-on-error",
+            "-f",
+        ])
+        .arg(&path)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    fs::remove_file(&path).unwrap();
+
+    assert!(stdout.contains("first record"), "{}", stdout);
+    assert!(stdout.contains("third record"), "{}", stdout);
+    assert!(
+        stdout.contains("[summary] 2 lines decrypted, 1 failed"),
+        "{}",
+        stdout
+    );
+    assert!(stderr.contains("[failed] line 2:"), "{}", stderr);
+}
@@ -0,0 +1,48 @@
+//! 确保 `ciphery::prelude` 导出了全部算法：只通过 prelude 构造并运行每一种算法
+
+use ciphery::prelude::*;
+
+/// 加密后再解密应还原原文
+fn assert_roundtrip(cipher: &dyn Cipher, text: &str) {
+    let encrypted = cipher.encrypt(text).unwrap();
+    assert_eq!(cipher.decrypt(&encrypted).unwrap(), text);
+}
+
+#[test]
+fn test_prelude_exports_every_cipher() {
+    assert_roundtrip(&Atbash::new(), "Attack at dawn");
+    assert_roundtrip(&Beaufort::new("FORTIFICATION").unwrap(), "Attack at dawn");
+    assert_roundtrip(&Caesar::new(3), "Attack at dawn");
+    assert_roundtrip(&Morse::new(), "ATTACK AT DAWN");
+    assert_roundtrip(&Nato::new(), "ATTACK AT DAWN");
+    assert_roundtrip(&Playfair::new("PLAYFAIR").unwrap(), "ATTACKATDAWN");
+    assert_roundtrip(&Polybius::new(), "ATTACK AT DAWN");
+    assert_roundtrip(&RailFence::new(3).unwrap(), "Attack at dawn");
+    assert_roundtrip(&Columnar::new("ZEBRAS").unwrap(), "Attack at dawn");
+    assert_roundtrip(&Feistel::new("key", 8).unwrap(), "Attack at dawn");
+    assert_roundtrip(&Reverse::new(), "Attack at dawn");
+    assert_roundtrip(&Vigenere::new("LEMON").unwrap(), "Attack at dawn");
+    assert_roundtrip(&Xor::new("key").unwrap(), "Attack at dawn");
+    assert_roundtrip(&Inverse(Caesar::new(5)), "Attack at dawn");
+
+    let pipeline = Pipeline::new()
+        .then(Box::new(Caesar::new(3)))
+        .unwrap()
+        .then(Box::new(Xor::new("key").unwrap()))
+        .unwrap();
+    assert_roundtrip(&pipeline, "Attack at dawn");
+    assert_eq!(pipeline.key_requirement(), KeyRequirement::None);
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn test_prelude_exports_only_matching() {
+    let cipher = OnlyMatching::new(Box::new(Caesar::new(3)), r"[a-z]+").unwrap();
+    assert_roundtrip(&cipher, "secret=abc 123");
+}
+
+#[test]
+fn test_prelude_exports_error() {
+    let err: CipherError = Vigenere::new("").unwrap_err();
+    assert!(matches!(err, CipherError::InvalidKey(_)));
+}
@@ -0,0 +1,68 @@
+//! XOR 原始字节输入（`--input-encoding raw`）的集成测试：直接运行编译好的二进制程序
+
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn test_decrypt_raw_xor_file() {
+    let path = std::env::temp_dir().join(format!("ciphery-xor-raw-{}.bin", std::process::id()));
+    // 直接写入异或后的原始字节；密钥含非 ASCII 字符，结果中会出现非 UTF-8 的字节
+    let key = "clé";
+    let raw: Vec<u8> = "Attack at dawn!"
+        .bytes()
+        .zip(key.bytes().cycle())
+        .map(|(b, k)| b ^ k)
+        .collect();
+    assert!(String::from_utf8(raw.clone()).is_err());
+    fs::write(&path, &raw).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_ciphery-bin"))
+        .args([
+            "decrypt",
+            "-a",
+            "xor",
+            "-k",
+            key,
+            "--input-encoding",
+            "raw",
+            "-f",
+        ])
+        .arg(&path)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    fs::remove_file(&path).unwrap();
+    assert!(stdout.contains("Attack at dawn!"), "{}", stdout);
+}
+
+#[test]
+fn test_decrypt_raw_xor_unicode_file() {
+    let path = std::env::temp_dir().join(format!(
+        "ciphery-xor-raw-unicode-{}.bin",
+        std::process::id()
+    ));
+    // 多字节字符被逐字节打散，只有读入完整文件后再做 UTF-8 校验才能还原
+    let raw = ciphery::xor::Xor::new("key").unwrap().encrypt_bytes("世界🦀");
+    assert!(String::from_utf8(raw.clone()).is_err());
+    fs::write(&path, &raw).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_ciphery-bin"))
+        .args([
+            "decrypt",
+            "-a",
+            "xor",
+            "-k",
+            "key",
+            "--input-encoding",
+            "raw",
+            "-f",
+        ])
+        .arg(&path)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    fs::remove_file(&path).unwrap();
+    assert!(stdout.contains("世界🦀"), "{}", stdout);
+}